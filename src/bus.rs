@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use hal::blocking::i2c::{Read, Write, WriteRead};
+use linux_hal::I2cdev;
+use linux_hal::i2cdev::linux::LinuxI2CError;
+
+use error::Error;
+use sync::lock;
+
+//Anything waiting this long for the bus is worth a warning, a transaction is normally well under a millisecond
+const SLOW_WAIT: Duration = Duration::from_millis(500);
+
+/// Owns every I2C bus the app talks to, each one is opened the first time a driver asks for it and
+/// shared by every driver on it from then on.
+pub struct BusManager {
+    buses: Mutex<HashMap<String, Arc<Bus>>>,
+}
+
+impl BusManager {
+    pub fn new() -> BusManager {
+        BusManager {
+            buses: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The bus at `path`, opening it if no one has used it yet
+    pub fn bus(&self, path: &str) -> Result<Arc<Bus>, Error> {
+        let mut buses = lock(&self.buses);
+        if let Some(bus) = buses.get(path) {
+            return Ok(Arc::clone(bus));
+        }
+        info!("Opening I2C bus {}", path);
+        let bus = Arc::new(Bus {
+            path: String::from(path),
+            lock: Arc::new(Mutex::new(0i32)),
+            state: Mutex::new(BusState {
+                dev: I2cdev::new(path)?,
+                waits: HashMap::new(),
+            }),
+        });
+        buses.insert(String::from(path), Arc::clone(&bus));
+        Ok(bus)
+    }
+
+    /// A handle for the device at `address` on the bus at `path`
+    pub fn proxy(&self, path: &str, address: u8) -> Result<I2cProxy, Error> {
+        Ok(I2cProxy {
+            bus: self.bus(path)?,
+            address,
+        })
+    }
+
+    /// Logs how long the devices on every bus have spent waiting for it
+    pub fn log_wait_stats(&self) {
+        for bus in lock(&self.buses).values() {
+            let state = lock(&bus.state);
+            for (address, stats) in state.waits.iter() {
+                info!("I2C {} device {:#04x}: {} transactions, {}ms total wait, {}ms longest wait",
+                      bus.path, address, stats.transactions, stats.total_wait.as_millis(), stats.max_wait.as_millis());
+            }
+        }
+    }
+}
+
+pub struct Bus {
+    path: String,
+    //Taken for every transaction, libs which talk to the bus through their own handle take it too
+    lock: Arc<Mutex<i32>>,
+    state: Mutex<BusState>,
+}
+
+struct BusState {
+    dev: I2cdev,
+    waits: HashMap<u8, WaitStats>,
+}
+
+/// Time the device at one address spent waiting for other devices to finish with the bus
+#[derive(Default)]
+struct WaitStats {
+    transactions: u64,
+    total_wait: Duration,
+    max_wait: Duration,
+}
+
+impl Bus {
+    /// For libs which open the bus themselves and only need to keep out of everyone else's way,
+    /// they must hold it for as long as they are using the bus.
+    pub fn shared_lock(&self) -> Arc<Mutex<i32>> {
+        Arc::clone(&self.lock)
+    }
+}
+
+/// Stands in for the bus in a device lib, every read and write is one transaction on the shared bus
+/// so transactions from different devices never interleave.
+pub struct I2cProxy {
+    bus: Arc<Bus>,
+    address: u8,
+}
+
+impl I2cProxy {
+    fn transaction<T, F>(&mut self, f: F) -> T
+        where F: FnOnce(&mut I2cdev) -> T {
+        let started = Instant::now();
+        let _guard = lock(&self.bus.lock);
+        let mut state = lock(&self.bus.state);
+        let waited = started.elapsed();
+        if waited >= SLOW_WAIT {
+            warn!("Device {:#04x} waited {}ms for I2C bus {}", self.address, waited.as_millis(), self.bus.path);
+        }
+        {
+            let stats = state.waits.entry(self.address).or_insert_with(WaitStats::default);
+            stats.transactions += 1;
+            stats.total_wait += waited;
+            if waited > stats.max_wait {
+                stats.max_wait = waited;
+            }
+        }
+        f(&mut state.dev)
+    }
+}
+
+impl Write for I2cProxy {
+    type Error = LinuxI2CError;
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), LinuxI2CError> {
+        self.transaction(|dev| dev.write(address, bytes))
+    }
+}
+
+impl Read for I2cProxy {
+    type Error = LinuxI2CError;
+
+    fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), LinuxI2CError> {
+        self.transaction(|dev| dev.read(address, buffer))
+    }
+}
+
+impl WriteRead for I2cProxy {
+    type Error = LinuxI2CError;
+
+    fn write_read(&mut self, address: u8, bytes: &[u8], buffer: &mut [u8]) -> Result<(), LinuxI2CError> {
+        self.transaction(|dev| dev.write_read(address, bytes, buffer))
+    }
+}
@@ -0,0 +1,104 @@
+use std::sync::mpsc::Sender;
+
+use serde_json::Value as JsonValue;
+
+use payload::Payload;
+
+/// Something a sensor was asked to do over the command topic.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum Command {
+    /// Take a measurement and send it now instead of waiting for the next publish tick
+    Measure,
+    /// Save the SGP30's baseline to disk now instead of at the end of the interval
+    SaveBaseline,
+    /// Throw away the SGP30's learned baseline and start learning it again from scratch
+    ResetBaseline,
+    /// Change how often a sensor's readings are sent, until the app is restarted
+    SetInterval { interval_secs: u64 },
+}
+
+impl Command {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Command::Measure => "measure",
+            Command::SaveBaseline => "save_baseline",
+            Command::ResetBaseline => "reset_baseline",
+            Command::SetInterval { .. } => "set_interval",
+        }
+    }
+}
+
+/// A command on its way to a sensor thread, the id is whatever the sender gave so they can match up the reply
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub id: Option<String>,
+    pub sensor: String,
+    pub command: Command,
+}
+
+impl Request {
+    /// Parses a message from the command topic, a JSON object with the sensor and command and any arguments, e.g.
+    /// `{"id": "1", "sensor": "bmp280", "command": "set_interval", "interval_secs": 600}`
+    pub fn parse(message: &[u8]) -> Result<Request, Reply> {
+        let value: JsonValue = serde_json::from_slice(message)
+            .map_err(|err| Reply::new(None, "", "", Err(format!("Command is not valid JSON: {}", err))))?;
+        let id = value.get("id").and_then(|id| id.as_str()).map(String::from);
+        let sensor = match value.get("sensor").and_then(|sensor| sensor.as_str()) {
+            Some(sensor) => String::from(sensor),
+            None => return Err(Reply::new(id, "", "", Err(String::from("Command has no sensor")))),
+        };
+        let command = match serde_json::from_value::<Command>(value) {
+            Ok(command) => command,
+            Err(err) => return Err(Reply::new(id, &sensor, "", Err(format!("Command is not valid: {}", err)))),
+        };
+        if let Command::SetInterval { interval_secs: 0 } = command {
+            return Err(Reply::new(id, &sensor, command.name(), Err(String::from("interval_secs must be greater than 0"))));
+        }
+        Ok(Request {
+            id,
+            sensor,
+            command,
+        })
+    }
+
+    pub fn reply(&self, result: Result<String, String>) -> Reply {
+        Reply::new(self.id.clone(), &self.sensor, self.command.name(), result)
+    }
+}
+
+/// Sent to the response topic for every command, `message` says what happened or why it failed
+#[derive(Debug, Clone, Serialize)]
+pub struct Reply {
+    pub id: Option<String>,
+    pub sensor: String,
+    pub command: String,
+    pub ok: bool,
+    pub message: String,
+}
+
+impl Reply {
+    fn new(id: Option<String>, sensor: &str, command: &str, result: Result<String, String>) -> Reply {
+        let (ok, message) = match result {
+            Ok(message) => (true, message),
+            Err(message) => (false, message),
+        };
+        Reply {
+            id,
+            sensor: String::from(sensor),
+            command: String::from(command),
+            ok,
+            message,
+        }
+    }
+
+    /// Sends the reply to the main thread to be published
+    pub fn send(self, sender: &Sender<Payload>) {
+        match sender.send(Payload::Reply(self)) {
+            Ok(_) => {}
+            Err(err) => {
+                error!("Failed to send message to main thread: {}", err);
+            }
+        }
+    }
+}
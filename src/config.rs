@@ -0,0 +1,725 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::fs::File;
+use std::path::Path;
+
+use error::{Error, ConfigError};
+
+// Every section and field is optional, anything left out of the config file falls back to the
+// values this app was originally hard coded with for the primary home auto Pi.
+//
+// Each sensor has an `enabled` flag, a disabled sensor is never created so it will never touch its bus or port,
+// and a `required` flag, if a required sensor fails to initialize the app will refuse to start.
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub location: u16,
+    pub reprobe_interval_secs: u64,
+    pub mqtt: MqttConfig,
+    pub spool: SpoolConfig,
+    pub home_assistant: HomeAssistantConfig,
+    pub state_topics: StateTopicsConfig,
+    /// Where readings are sent besides MQ, each on its own thread
+    pub sinks: Vec<SinkConfig>,
+    pub bmp280: Bmp280Config,
+    pub htu21d: Htu21dConfig,
+    pub sgp30: Sgp30Config,
+    pub geiger: GeigerConfig,
+    pub sds011: Sds011Config,
+    pub thermostat: ThermostatConfig,
+    pub as3935: As3935Config,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            location: 2,
+            reprobe_interval_secs: 60,
+            mqtt: MqttConfig::default(),
+            spool: SpoolConfig::default(),
+            home_assistant: HomeAssistantConfig::default(),
+            state_topics: StateTopicsConfig::default(),
+            sinks: Vec::new(),
+            bmp280: Bmp280Config::default(),
+            htu21d: Htu21dConfig::default(),
+            sgp30: Sgp30Config::default(),
+            geiger: GeigerConfig::default(),
+            sds011: Sds011Config::default(),
+            thermostat: ThermostatConfig::default(),
+            as3935: As3935Config::default(),
+        }
+    }
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Config, Error> {
+        let file = File::open(path).map_err(ConfigError::Io)?;
+        let config: Config = serde_yaml::from_reader(file).map_err(ConfigError::Yaml)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// The first history sink, where `indoor_sensors query` reads from
+    pub fn history(&self) -> Option<&HistorySinkConfig> {
+        self.sinks.iter()
+            .filter_map(|sink| match sink.kind {
+                SinkKind::History(ref history) => Some(history),
+                _ => None,
+            })
+            .next()
+    }
+
+    /// The topic a sensor's readings are sent to, by the name the sensor describes itself with
+    pub fn topic_for(&self, sensor: &str) -> Option<&str> {
+        match sensor {
+            "bmp280" => Some(&self.bmp280.topic),
+            "htu21d" => Some(&self.htu21d.topic),
+            "sgp30" => Some(&self.sgp30.topic),
+            "geiger" => Some(&self.geiger.topic),
+            "sds011" => Some(&self.sds011.topic),
+            "thermostat" => Some(&self.thermostat.topic),
+            _ => None,
+        }
+    }
+
+    /// Whether a sensor is enabled, by the name the sensor describes itself with
+    pub fn is_enabled(&self, sensor: &str) -> bool {
+        match sensor {
+            "bmp280" => self.bmp280.enabled,
+            "htu21d" => self.htu21d.enabled,
+            "sgp30" => self.sgp30.enabled,
+            "geiger" => self.geiger.enabled,
+            "sds011" => self.sds011.enabled,
+            "thermostat" => self.thermostat.enabled,
+            "as3935" => self.as3935.enabled,
+            _ => false,
+        }
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        let intervals = [
+            ("bmp280", self.bmp280.interval_secs),
+            ("htu21d", self.htu21d.interval_secs),
+            ("sgp30", self.sgp30.interval_secs),
+            ("geiger", self.geiger.interval_secs),
+            ("sds011", self.sds011.interval_secs),
+            ("thermostat", self.thermostat.interval_secs),
+        ];
+        if self.reprobe_interval_secs == 0 {
+            return Err(Error::from(ConfigError::Invalid(String::from("reprobe_interval_secs must be greater than 0"))));
+        }
+        if self.mqtt.keepalive_secs == 0 {
+            return Err(Error::from(ConfigError::Invalid(String::from("mqtt keepalive_secs must be greater than 0"))));
+        }
+        if self.mqtt.password.is_some() && self.mqtt.username.is_none() {
+            return Err(Error::from(ConfigError::Invalid(String::from("mqtt password is set without a username"))));
+        }
+        if let Some(ref tls) = self.mqtt.tls {
+            if tls.cert_file.is_some() != tls.key_file.is_some() {
+                return Err(Error::from(ConfigError::Invalid(String::from("mqtt tls needs both cert_file and key_file, or neither"))));
+            }
+        }
+        if self.spool.max_messages == 0 {
+            return Err(Error::from(ConfigError::Invalid(String::from("spool max_messages must be greater than 0"))));
+        }
+        for &(name, interval) in intervals.iter() {
+            if interval == 0 {
+                return Err(Error::from(ConfigError::Invalid(format!("{} interval_secs must be greater than 0", name))));
+            }
+        }
+        for (i, sink) in self.sinks.iter().enumerate() {
+            if self.sinks[..i].iter().any(|other| other.name == sink.name) {
+                return Err(Error::from(ConfigError::Invalid(format!("sink name {} is used more than once", sink.name))));
+            }
+            if sink.queue_size == 0 {
+                return Err(Error::from(ConfigError::Invalid(format!("sink {} queue_size must be greater than 0", sink.name))));
+            }
+            match sink.kind {
+                SinkKind::Http(ref http) => {
+                    if http.url.is_empty() {
+                        return Err(Error::from(ConfigError::Invalid(format!("sink {} needs a url", sink.name))));
+                    }
+                }
+                SinkKind::Api(_) | SinkKind::Prometheus(_) => {}
+                SinkKind::Influx(ref influx) => {
+                    if influx.url.is_empty() {
+                        return Err(Error::from(ConfigError::Invalid(format!("sink {} needs a url", sink.name))));
+                    }
+                    if influx.batch_size == 0 || influx.flush_interval_secs == 0 {
+                        return Err(Error::from(ConfigError::Invalid(format!("sink {} batch_size and flush_interval_secs must be greater than 0", sink.name))));
+                    }
+                    if influx.max_buffered_lines < influx.batch_size {
+                        return Err(Error::from(ConfigError::Invalid(format!("sink {} max_buffered_lines must be at least batch_size", sink.name))));
+                    }
+                }
+                SinkKind::Loki(ref loki) => {
+                    if loki.url.is_empty() {
+                        return Err(Error::from(ConfigError::Invalid(format!("sink {} needs a url", sink.name))));
+                    }
+                    if loki.batch_size == 0 || loki.flush_interval_secs == 0 {
+                        return Err(Error::from(ConfigError::Invalid(format!("sink {} batch_size and flush_interval_secs must be greater than 0", sink.name))));
+                    }
+                    if loki.max_buffered_entries < loki.batch_size {
+                        return Err(Error::from(ConfigError::Invalid(format!("sink {} max_buffered_entries must be at least batch_size", sink.name))));
+                    }
+                }
+                SinkKind::Archive(ref archive) => {
+                    if archive.dir.is_empty() {
+                        return Err(Error::from(ConfigError::Invalid(format!("sink {} needs a dir", sink.name))));
+                    }
+                }
+                SinkKind::History(ref history) => {
+                    if history.path.is_empty() {
+                        return Err(Error::from(ConfigError::Invalid(format!("sink {} needs a path", sink.name))));
+                    }
+                    if history.raw_retention_days == 0 {
+                        return Err(Error::from(ConfigError::Invalid(format!("sink {} raw_retention_days must be greater than 0", sink.name))));
+                    }
+                }
+            }
+        }
+        //The sds011 is powered on for the last minute of its interval, anything shorter doesn't leave time to warm up
+        if self.sds011.interval_secs < 60 {
+            return Err(Error::from(ConfigError::Invalid(String::from("sds011 interval_secs must be at least 60"))));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct MqttConfig {
+    pub host: String,
+    pub port: u16,
+    /// Every client on a broker needs its own id, left out it is made from the hostname so two instances don't kick each other off
+    pub client_id: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub keepalive_secs: u64,
+    pub tls: Option<TlsConfig>,
+    /// Retained `online` while we are connected and `offline` when we aren't, each sensor gets its own under it
+    pub availability_topic: String,
+    /// Commands for the sensors are read from here, left out no commands are accepted
+    pub command_topic: Option<String>,
+    /// Every command gets a reply here saying what happened
+    pub response_topic: String,
+    /// How readings are encoded, unless the topic they're sent to has its own format in `topic_formats`
+    pub format: Format,
+    pub topic_formats: BTreeMap<String, Format>,
+}
+
+impl Default for MqttConfig {
+    fn default() -> Self {
+        MqttConfig {
+            host: String::from("localhost"),
+            port: 1883,
+            client_id: None,
+            username: None,
+            password: None,
+            keepalive_secs: 60,
+            tls: None,
+            availability_topic: String::from("/ws/2/indoor_sensors/availability"),
+            command_topic: None,
+            response_topic: String::from("/ws/2/indoor_sensors/response"),
+            format: Format::Json,
+            topic_formats: BTreeMap::new(),
+        }
+    }
+}
+
+impl MqttConfig {
+    pub fn sensor_availability_topic(&self, sensor: &str) -> String {
+        format!("{}/{}", self.availability_topic, sensor)
+    }
+
+    pub fn format_for(&self, topic: &str) -> Format {
+        self.topic_formats.get(topic).cloned().unwrap_or(self.format)
+    }
+
+    pub fn client_id(&self) -> String {
+        match self.client_id {
+            Some(ref client_id) => client_id.clone(),
+            None => {
+                match hostname() {
+                    Some(hostname) => format!("indoor_sensors-{}", hostname),
+                    None => String::from("indoor_sensors"),
+                }
+            }
+        }
+    }
+}
+
+pub fn hostname() -> Option<String> {
+    fs::read_to_string("/proc/sys/kernel/hostname").ok().map(|hostname| String::from(hostname.trim()))
+}
+
+/// PEM files, the client certificate and key are only needed if the broker wants clients to authenticate with one
+#[derive(Debug, Clone, Deserialize)]
+pub struct TlsConfig {
+    pub ca_file: String,
+    pub cert_file: Option<String>,
+    pub key_file: Option<String>,
+}
+
+/// Where messages go while the MQ broker can't be reached
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SpoolConfig {
+    pub dir: String,
+    pub max_messages: usize,
+    pub overflow: Overflow,
+}
+
+impl Default for SpoolConfig {
+    fn default() -> Self {
+        SpoolConfig {
+            dir: String::from("/var/lib/indoor_sensors/spool"),
+            max_messages: 10000,
+            overflow: Overflow::DropOldest,
+        }
+    }
+}
+
+/// One sink, what's sent to it is filtered by sensor and quantity names
+#[derive(Debug, Clone, Deserialize)]
+pub struct SinkConfig {
+    pub name: String,
+    /// Only readings and events from these sensors are sent, left empty everything is
+    #[serde(default)]
+    pub sensors: Vec<String>,
+    /// Only these quantities are sent, left empty everything is
+    #[serde(default)]
+    pub quantities: Vec<String>,
+    /// How many deliveries can wait while the sink is slow or failing, once it's full new ones are dropped
+    #[serde(default = "default_queue_size")]
+    pub queue_size: usize,
+    #[serde(flatten)]
+    pub kind: SinkKind,
+}
+
+fn default_queue_size() -> usize {
+    1000
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SinkKind {
+    Api(ApiSinkConfig),
+    Http(HttpSinkConfig),
+    Prometheus(PrometheusSinkConfig),
+    Influx(InfluxSinkConfig),
+    Loki(LokiSinkConfig),
+    Archive(ArchiveSinkConfig),
+    History(HistorySinkConfig),
+}
+
+/// POSTs every message to a URL
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct HttpSinkConfig {
+    pub url: String,
+    pub format: Format,
+    pub timeout_ms: u64,
+}
+
+impl Default for HttpSinkConfig {
+    fn default() -> Self {
+        HttpSinkConfig {
+            url: String::new(),
+            format: Format::Json,
+            timeout_ms: 5000,
+        }
+    }
+}
+
+/// Serves the latest readings and the state of every sensor as JSON on /api
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ApiSinkConfig {
+    pub listen: String,
+}
+
+impl Default for ApiSinkConfig {
+    fn default() -> Self {
+        ApiSinkConfig {
+            listen: String::from("0.0.0.0:8080"),
+        }
+    }
+}
+
+/// Serves the latest readings for Prometheus to scrape on /metrics
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct PrometheusSinkConfig {
+    pub listen: String,
+}
+
+impl Default for PrometheusSinkConfig {
+    fn default() -> Self {
+        PrometheusSinkConfig {
+            listen: String::from("0.0.0.0:9184"),
+        }
+    }
+}
+
+/// Writes line protocol to InfluxDB, the url is the whole write endpoint with its query string,
+/// e.g. http://influx:8086/api/v2/write?org=home&bucket=sensors or http://influx:8086/write?db=sensors
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct InfluxSinkConfig {
+    pub url: String,
+    /// InfluxDB 2 API token, v1 credentials go in the url as u and p
+    pub token: Option<String>,
+    pub batch_size: usize,
+    pub flush_interval_secs: u64,
+    /// How many lines are kept while InfluxDB can't be reached, the oldest are dropped after that
+    pub max_buffered_lines: usize,
+    pub gzip: bool,
+    pub timeout_ms: u64,
+}
+
+impl Default for InfluxSinkConfig {
+    fn default() -> Self {
+        InfluxSinkConfig {
+            url: String::new(),
+            token: None,
+            batch_size: 500,
+            flush_interval_secs: 10,
+            max_buffered_lines: 10000,
+            gzip: true,
+            timeout_ms: 5000,
+        }
+    }
+}
+
+/// Pushes events and sensor health to Loki, the url is the push endpoint e.g. http://loki:3100/loki/api/v1/push
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct LokiSinkConfig {
+    pub url: String,
+    /// Sent as X-Scope-OrgID for a multi-tenant Loki
+    pub tenant: Option<String>,
+    /// The host label, defaults to the hostname
+    pub host: Option<String>,
+    /// Push every reading as a logfmt line as well
+    pub readings: bool,
+    pub batch_size: usize,
+    pub flush_interval_secs: u64,
+    /// How many entries are kept while Loki can't be reached, the oldest are dropped after that
+    pub max_buffered_entries: usize,
+    pub gzip: bool,
+    pub timeout_ms: u64,
+}
+
+impl Default for LokiSinkConfig {
+    fn default() -> Self {
+        LokiSinkConfig {
+            url: String::new(),
+            tenant: None,
+            host: None,
+            readings: false,
+            batch_size: 100,
+            flush_interval_secs: 5,
+            max_buffered_entries: 10000,
+            gzip: true,
+            timeout_ms: 5000,
+        }
+    }
+}
+
+/// Keeps every reading in a file per day under `dir`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ArchiveSinkConfig {
+    pub dir: String,
+    pub format: ArchiveFormat,
+    /// Files older than this many days are deleted, 0 keeps them forever
+    pub retention_days: u64,
+    /// Gzip each day's file once the day is over
+    pub compress: bool,
+}
+
+impl Default for ArchiveSinkConfig {
+    fn default() -> Self {
+        ArchiveSinkConfig {
+            dir: String::from("/var/lib/indoor_sensors/archive"),
+            format: ArchiveFormat::Jsonl,
+            retention_days: 90,
+            compress: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveFormat {
+    /// A JSON object per reading per line
+    Jsonl,
+    /// timestamp,location,sensor,quantity,value,unit with a header
+    Csv,
+}
+
+/// Keeps readings in a SQLite database for `indoor_sensors query`, averaged per minute and rolled up into
+/// hourly min/avg/max
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct HistorySinkConfig {
+    pub path: String,
+    /// How long the per minute values are kept
+    pub raw_retention_days: u64,
+    /// How long the hourly rollups are kept, 0 keeps them forever
+    pub rollup_retention_days: u64,
+}
+
+impl Default for HistorySinkConfig {
+    fn default() -> Self {
+        HistorySinkConfig {
+            path: String::from("/var/lib/indoor_sensors/history.db"),
+            raw_retention_days: 30,
+            rollup_retention_days: 730,
+        }
+    }
+}
+
+/// How readings are encoded into messages
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Format {
+    /// The sensor_lib structs as JSON, what every reading was always sent as
+    Json,
+    /// InfluxDB line protocol, for Telegraf and the like
+    Influx,
+    /// The sensor, location, timestamp and a map of quantity to value as CBOR
+    Cbor,
+    /// Each quantity on its own subtopic with just the number as the body
+    Plain,
+}
+
+/// What to lose once the spool is full
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Overflow {
+    DropOldest,
+    DropNewest,
+}
+
+/// Home Assistant MQTT discovery, every quantity a sensor produces shows up as its own entity
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct HomeAssistantConfig {
+    pub enabled: bool,
+    /// Home Assistant's discovery prefix, it only needs changing if it was changed in Home Assistant too
+    pub discovery_prefix: String,
+    /// Each sensor's latest readings are sent as one JSON object to a topic under this for the entities to read
+    pub state_topic: String,
+}
+
+impl Default for HomeAssistantConfig {
+    fn default() -> Self {
+        HomeAssistantConfig {
+            enabled: false,
+            discovery_prefix: String::from("homeassistant"),
+            state_topic: String::from("/ws/2/indoor_sensors/state"),
+        }
+    }
+}
+
+impl HomeAssistantConfig {
+    pub fn sensor_state_topic(&self, sensor: &str) -> String {
+        format!("{}/{}", self.state_topic, sensor)
+    }
+}
+
+/// A retained topic per quantity carrying just the latest value, sent as well as the grouped topics
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct StateTopicsConfig {
+    pub enabled: bool,
+    /// Topics are <prefix>/<location>/<quantity>
+    pub prefix: String,
+}
+
+impl Default for StateTopicsConfig {
+    fn default() -> Self {
+        StateTopicsConfig {
+            enabled: false,
+            prefix: String::from("indoor"),
+        }
+    }
+}
+
+impl StateTopicsConfig {
+    pub fn topic_for(&self, location: u16, quantity: &str) -> String {
+        format!("{}/{}/{}", self.prefix, location, quantity)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Bmp280Config {
+    pub enabled: bool,
+    pub required: bool,
+    pub device: String,
+    pub topic: String,
+    pub pressure_id: u16,
+    pub interval_secs: u64,
+}
+
+impl Default for Bmp280Config {
+    fn default() -> Self {
+        Bmp280Config {
+            enabled: true,
+            required: false,
+            device: String::from("/dev/i2c-1"),
+            topic: String::from("/ws/2/grp/generic"),
+            pressure_id: 54,
+            interval_secs: 300,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Htu21dConfig {
+    pub enabled: bool,
+    pub required: bool,
+    pub device: String,
+    pub topic: String,
+    pub interval_secs: u64,
+}
+
+impl Default for Htu21dConfig {
+    fn default() -> Self {
+        Htu21dConfig {
+            enabled: true,
+            required: false,
+            device: String::from("/dev/i2c-1"),
+            topic: String::from("/ws/2/grp/temp_humidity"),
+            interval_secs: 60,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Sgp30Config {
+    pub enabled: bool,
+    pub required: bool,
+    pub device: String,
+    pub address: u8,
+    pub topic: String,
+    pub co2_id: u16,
+    pub tvoc_id: u16,
+    //The sensor is always measured once a second, this is only how often the averaged values are sent
+    pub interval_secs: u64,
+    pub baseline_dir: String,
+}
+
+impl Default for Sgp30Config {
+    fn default() -> Self {
+        Sgp30Config {
+            enabled: true,
+            required: false,
+            device: String::from("/dev/i2c-1"),
+            address: 0x58,
+            topic: String::from("/ws/2/grp/generic"),
+            co2_id: 52,
+            tvoc_id: 53,
+            interval_secs: 60,
+            baseline_dir: String::from("/var/lib/indoor_sensors"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct GeigerConfig {
+    pub enabled: bool,
+    pub required: bool,
+    pub device: String,
+    pub topic: String,
+    pub cpm_id: u16,
+    //The counter reports once a second, this is only how often the averaged value is sent
+    pub interval_secs: u64,
+}
+
+impl Default for GeigerConfig {
+    fn default() -> Self {
+        GeigerConfig {
+            enabled: true,
+            required: false,
+            device: String::from("/dev/ttyUSB0"),
+            topic: String::from("/ws/2/grp/generic"),
+            cpm_id: 55,
+            interval_secs: 60,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Sds011Config {
+    pub enabled: bool,
+    pub required: bool,
+    pub device: String,
+    pub topic: String,
+    //Length of one duty cycle, the sensor is only powered for the last minute of it
+    pub interval_secs: u64,
+}
+
+impl Default for Sds011Config {
+    fn default() -> Self {
+        Sds011Config {
+            enabled: true,
+            required: false,
+            device: String::from("/dev/serial0"),
+            topic: String::from("/ws/2/grp/air_particulate"),
+            interval_secs: 300,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ThermostatConfig {
+    pub enabled: bool,
+    pub required: bool,
+    pub url: String,
+    pub topic: String,
+    pub interval_secs: u64,
+    pub timeout_ms: u64,
+}
+
+impl Default for ThermostatConfig {
+    fn default() -> Self {
+        ThermostatConfig {
+            enabled: true,
+            required: false,
+            url: String::from("http://172.20.30.30/tstat"),
+            topic: String::from("/ws/2/grp/thermostat"),
+            interval_secs: 60,
+            timeout_ms: 20000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct As3935Config {
+    pub enabled: bool,
+    pub required: bool,
+    pub i2c_bus: u8,
+    pub irq_pin: u8,
+}
+
+impl Default for As3935Config {
+    fn default() -> Self {
+        As3935Config {
+            enabled: false,
+            required: false,
+            i2c_bus: 1,
+            irq_pin: 6,
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use std::fmt::{Display, Formatter};
+
+use std::error::Error as StdError;
+use std::io::Error as IoError;
+use std::io::ErrorKind;
+use std::num::ParseIntError;
+
+use linux_hal::i2cdev::linux::LinuxI2CError;
+use serial::core::Error as SerialError;
+use rppal::gpio::Error as GpioError;
+use rppal::i2c::Error as RppalI2cError;
+use mio_httpc::Error as MioError;
+use serde_json::Error as JsonError;
+use serde_yaml::Error as YamlError;
+use rusqlite::Error as SqliteError;
+
+/// One error type for every sensor driver and the config, the original error is kept so callers
+/// can match on the kind of failure instead of the log text.
+#[derive(Debug)]
+pub enum Error {
+    /// Talking to the device over I2C, serial or GPIO failed
+    Bus(BusError),
+    /// The device answered but the checksum on the data did not match
+    Crc,
+    /// The device did not answer in time
+    Timeout,
+    /// The device answered with something we didn't expect, the message says what
+    Protocol(String),
+    /// The driver was used before `init` succeeded
+    NotInitialized,
+    Config(ConfigError),
+    Io(IoError),
+    Parse(ParseError),
+    Http(MioError),
+    /// Reading or writing the history database failed
+    Database(SqliteError),
+    /// The device or its driver lib reported a failure of its own
+    Device(String),
+    /// The sensor was sent a command it has nothing to do for
+    Unsupported,
+}
+
+#[derive(Debug)]
+pub enum BusError {
+    I2c(LinuxI2CError),
+    RppalI2c(RppalI2cError),
+    Serial(SerialError),
+    Gpio(GpioError),
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(IoError),
+    Yaml(YamlError),
+    Invalid(String),
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+    Int(ParseIntError),
+    Json(JsonError),
+}
+
+impl Error {
+    /// Errors which mean the device is in a bad state and should be initialized again before we trust it
+    pub fn needs_reinit(&self) -> bool {
+        match *self {
+            Error::Crc | Error::NotInitialized => true,
+            _ => false,
+        }
+    }
+}
+
+impl From<LinuxI2CError> for Error {
+    fn from(err: LinuxI2CError) -> Self {
+        Error::Bus(BusError::I2c(err))
+    }
+}
+
+impl From<RppalI2cError> for Error {
+    fn from(err: RppalI2cError) -> Self {
+        Error::Bus(BusError::RppalI2c(err))
+    }
+}
+
+impl From<SerialError> for Error {
+    fn from(err: SerialError) -> Self {
+        Error::Bus(BusError::Serial(err))
+    }
+}
+
+impl From<GpioError> for Error {
+    fn from(err: GpioError) -> Self {
+        Error::Bus(BusError::Gpio(err))
+    }
+}
+
+impl From<IoError> for Error {
+    fn from(err: IoError) -> Self {
+        if err.kind() == ErrorKind::TimedOut {
+            Error::Timeout
+        } else {
+            Error::Io(err)
+        }
+    }
+}
+
+impl From<ParseIntError> for Error {
+    fn from(err: ParseIntError) -> Self {
+        Error::Parse(ParseError::Int(err))
+    }
+}
+
+impl From<JsonError> for Error {
+    fn from(err: JsonError) -> Self {
+        Error::Parse(ParseError::Json(err))
+    }
+}
+
+impl From<MioError> for Error {
+    fn from(err: MioError) -> Self {
+        Error::Http(err)
+    }
+}
+
+impl From<SqliteError> for Error {
+    fn from(err: SqliteError) -> Self {
+        Error::Database(err)
+    }
+}
+
+impl From<ConfigError> for Error {
+    fn from(err: ConfigError) -> Self {
+        Error::Config(err)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        match *self {
+            Error::Bus(ref err) => write!(f, "Bus Error: {}", err),
+            Error::Crc => write!(f, "CRC Error"),
+            Error::Timeout => write!(f, "Timed out waiting for the device"),
+            Error::Protocol(ref message) => write!(f, "Protocol Error: {}", message),
+            Error::NotInitialized => write!(f, "Not Initialized"),
+            Error::Config(ref err) => write!(f, "Config Error: {}", err),
+            Error::Io(ref err) => write!(f, "{}", err),
+            Error::Parse(ref err) => write!(f, "Parse Error: {}", err),
+            Error::Http(ref err) => write!(f, "HTTP Error: {}", err),
+            Error::Database(ref err) => write!(f, "Database Error: {}", err),
+            Error::Device(ref message) => write!(f, "{}", message),
+            Error::Unsupported => write!(f, "Not supported by this sensor"),
+        }
+    }
+}
+
+impl Display for BusError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        match *self {
+            BusError::I2c(ref err) => write!(f, "I2C: {}", err),
+            BusError::RppalI2c(ref err) => write!(f, "I2C: {}", err),
+            BusError::Serial(ref err) => write!(f, "Serial: {}", err),
+            BusError::Gpio(ref err) => write!(f, "GPIO: {}", err),
+        }
+    }
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        match *self {
+            ConfigError::Io(ref err) => write!(f, "Failed to open config file: {}", err),
+            ConfigError::Yaml(ref err) => write!(f, "Failed to parse config file: {}", err),
+            ConfigError::Invalid(ref message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        match *self {
+            ParseError::Int(ref err) => write!(f, "{}", err),
+            ParseError::Json(ref err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match *self {
+            Error::Bus(BusError::I2c(ref err)) => Some(err),
+            Error::Bus(BusError::RppalI2c(ref err)) => Some(err),
+            Error::Bus(BusError::Serial(ref err)) => Some(err),
+            Error::Bus(BusError::Gpio(ref err)) => Some(err),
+            Error::Config(ConfigError::Io(ref err)) => Some(err),
+            Error::Config(ConfigError::Yaml(ref err)) => Some(err),
+            Error::Io(ref err) => Some(err),
+            Error::Database(ref err) => Some(err),
+            Error::Parse(ParseError::Int(ref err)) => Some(err),
+            Error::Parse(ParseError::Json(ref err)) => Some(err),
+            _ => None,
+        }
+    }
+}
@@ -6,10 +6,13 @@ extern crate chrono;
 extern crate embedded_hal as hal;
 extern crate linux_embedded_hal as linux_hal;
 
-extern crate mosquitto_client;
+extern crate rumqttc;
+extern crate serde;
+#[macro_use]
 extern crate serde_derive;
 extern crate serde_yaml;
 extern crate serde_json;
+extern crate serde_cbor;
 extern crate dtoa;
 
 extern crate htu21d;
@@ -23,17 +26,50 @@ extern crate as3935;
 extern crate serial;
 
 extern crate mio_httpc;
+extern crate flate2;
+#[macro_use]
+extern crate rusqlite;
+
+extern crate signal_hook;
+extern crate tiny_http;
 
 use std::sync::{Arc, Mutex};
 use std::sync::mpsc;
+use std::sync::mpsc::RecvTimeoutError;
+use std::time::{Duration, Instant};
 use std::f32::NAN;
 use std::path::Path;
+use std::env;
+use std::process;
 
-use mosquitto_client::Mosquitto;
-
+mod bus;
+mod command;
+mod config;
+mod error;
+mod mqtt;
+mod payload;
+mod publisher;
+mod output;
+mod query;
+mod server;
+mod shutdown;
+mod sink;
+mod spool;
+mod sync;
 mod threads;
 
-use threads::bmp280::{Bmp280, Error};
+use bus::BusManager;
+use command::{Request, Reply};
+use config::Config;
+use mqtt::Mqtt;
+use payload::{Payload, Control, Status};
+use publisher::Publisher;
+use shutdown::Shutdown;
+use sink::Sinks;
+use spool::Spool;
+
+use threads::supervisor::Supervisor;
+use threads::bmp280::Bmp280;
 use threads::htu21d::Htu21d;
 use threads::sgp30::Sgp30;
 use threads::geiger::Geiger;
@@ -41,13 +77,24 @@ use threads::sds011::Sds011;
 use threads::radiothermostat::RadioThermostat;
 use threads::as3935::As3935;
 
-
-pub struct Payload {
-    queue: String,
-    bytes: String,
-}
+//How often to try sending spooled messages, and how many to send each time
+const REPLAY_INTERVAL: Duration = Duration::from_secs(10);
+const REPLAY_BATCH: usize = 100;
 
 fn main() {
+    //Subcommands run and exit before the logger is set up, it would write over their output on the console
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().map_or(false, |arg| arg == "query") {
+        let config = match Config::load(config_path()) {
+            Ok(config) => Some(config),
+            Err(err) => {
+                eprintln!("Failed to load config from {}: {}", config_path().display(), err);
+                None
+            }
+        };
+        process::exit(query::run(&args[1..], config));
+    }
+
     //Check if there is a logging config configured in /etc if not just use one local to the app
     let etc_config = Path::new("/etc/indoor_sensors/log4rs.yml");
     let log_config_path = if let true = etc_config.exists() {
@@ -58,98 +105,306 @@ fn main() {
     log4rs::init_file(log_config_path, Default::default()).expect("Failed to init logger");
     info!("Starting indoor_sensors");
 
-
-    let (sender, receiver) = mpsc::channel::<Payload>();
-    let i2c_mutex = Arc::new(Mutex::new(0i32));
-
-    //FIXME instead of unrwapping all of these we could check for errors so the progam can run missing a sensor
-    match Bmp280::new(mpsc::Sender::clone(&sender), Arc::clone(&i2c_mutex)) {
-        Ok(bme280) => {
-            Bmp280::start_thread(bme280);
+    let config_path = config_path();
+    let config = match Config::load(config_path) {
+        Ok(config) => {
+            info!("Loaded config from {}", config_path.display());
+            config
         }
         Err(err) => {
-            error!("Failed to create bmp280: {}", err)
+            error!("Failed to load config from {}: {}", config_path.display(), err);
+            panic!("Failed to load config from {}: {}", config_path.display(), err);
         }
     };
-    let humidity_mutex = Arc::new(Mutex::new((NAN, NAN)));
-    match Htu21d::new(mpsc::Sender::clone(&sender), Arc::clone(&i2c_mutex), Arc::clone(&humidity_mutex)) {
-        Ok(htu21d) => {
-            Htu21d::start_thread(htu21d);
-        }
+
+
+    let (sender, receiver) = mpsc::channel::<Payload>();
+    let buses = Arc::new(BusManager::new());
+
+    let shutdown = Shutdown::new();
+    match shutdown::handle_signals(shutdown.clone(), mpsc::Sender::clone(&sender)) {
+        Ok(_) => {}
         Err(err) => {
-            error!("Failed to create bmp280: {}", err)
+            error!("Failed to register signal handlers, the app will not shut down cleanly: {}", err);
         }
     }
-    match Sgp30::new(mpsc::Sender::clone(&sender), Arc::clone(&i2c_mutex), Arc::clone(&humidity_mutex)) {
-        Ok(sgp30) => {
-            Sgp30::start_thread(sgp30);
-        }
-        Err(err) => {
-            error!("Failed to create sgp30: {}", err)
-        }
+    let mut supervisor = Supervisor::new(mpsc::Sender::clone(&sender), shutdown.clone(), Duration::from_secs(config.reprobe_interval_secs));
+
+    //Each driver is created by a closure owning its own clones of the shared state so the supervisor can
+    //build it again from scratch if its thread has to be restarted
+    if config.bmp280.enabled {
+        let (buses, bmp280_config) = (Arc::clone(&buses), config.bmp280.clone());
+        supervisor.start(move || Bmp280::new(Arc::clone(&buses), bmp280_config.clone()), config.bmp280.required);
+    } else {
+        info!("bmp280 is disabled in the config, skipping it");
     }
-    match Geiger::new(mpsc::Sender::clone(&sender)) {
-        Ok(geiger) => {
-            Geiger::start_thread(geiger);
-        }
-        Err(err) => {
-            error!("Failed to create geiger: {}", err)
-        }
+    let humidity_mutex = Arc::new(Mutex::new((NAN, NAN)));
+    if config.htu21d.enabled {
+        let (buses, humidity_mutex, htu21d_config) = (Arc::clone(&buses), Arc::clone(&humidity_mutex), config.htu21d.clone());
+        supervisor.start(move || Htu21d::new(Arc::clone(&buses), Arc::clone(&humidity_mutex), htu21d_config.clone()), config.htu21d.required);
+    } else {
+        info!("htu21d is disabled in the config, skipping it");
+    }
+    if config.sgp30.enabled {
+        let (buses, humidity_mutex, sgp30_config) = (Arc::clone(&buses), Arc::clone(&humidity_mutex), config.sgp30.clone());
+        supervisor.start(move || Sgp30::new(Arc::clone(&buses), Arc::clone(&humidity_mutex), sgp30_config.clone()), config.sgp30.required);
+    } else {
+        info!("sgp30 is disabled in the config, skipping it");
+    }
+    if config.geiger.enabled {
+        let geiger_config = config.geiger.clone();
+        supervisor.start(move || Geiger::new(geiger_config.clone()), config.geiger.required);
+    } else {
+        info!("geiger is disabled in the config, skipping it");
+    }
+    if config.sds011.enabled {
+        let sds011_config = config.sds011.clone();
+        supervisor.start(move || Sds011::new(sds011_config.clone()), config.sds011.required);
+    } else {
+        info!("sds011 is disabled in the config, skipping it");
+    }
+    if config.thermostat.enabled {
+        let thermostat_config = config.thermostat.clone();
+        supervisor.start(move || RadioThermostat::new(thermostat_config.clone()), config.thermostat.required);
+    } else {
+        info!("radio thermostat is disabled in the config, skipping it");
+    }
+    if config.as3935.enabled {
+        let (buses, as3935_config) = (Arc::clone(&buses), config.as3935.clone());
+        supervisor.start(move || As3935::new(Arc::clone(&buses), as3935_config.clone()), config.as3935.required);
+    } else {
+        info!("as3935 is disabled in the config, skipping it");
     }
-    match Sds011::new(mpsc::Sender::clone(&sender)) {
-        Ok(sds011) => {
-            Sds011::start_thread(sds011);
+
+    //TODO Average temp/humidity values?
+
+    //Without a spool messages are dropped when the broker is down, which is better than not reading the sensors at all
+    let mut spool = match Spool::open(&config.spool) {
+        Ok(spool) => Some(spool),
+        Err(err) => {
+            error!("Failed to open the spool in {}: {}, messages will be dropped while MQ is unavailable", config.spool.dir, err);
+            None
         }
+    };
+
+    let mut sinks = Sinks::start(&config, &shutdown);
+
+    let mut mqtt = match Mqtt::connect(&config.mqtt) {
+        Ok(mqtt) => mqtt,
         Err(err) => {
-            error!("Failed to create sds011: {}", err)
+            error!("Failed to set up the MQ client: {}", err);
+            panic!("Failed to set up the MQ client: {}", err);
+        }
+    };
+
+    //Retained so Home Assistant picks them up whenever it starts, the MQ client sends them again on every connect
+    if config.home_assistant.enabled {
+        for (topic, body) in output::home_assistant::discovery(&config) {
+            debug!("Announcing '{}' to Home Assistant", topic);
+            mqtt.set_retained(&topic, body.as_bytes());
         }
     }
-    match RadioThermostat::new(mpsc::Sender::clone(&sender)) {
-        Ok(rt) => {
-            RadioThermostat::start_thread(rt);
+
+    //Wake up at least once a second so the supervisor can check on the sensor threads
+    let mut last_replay = Instant::now();
+    loop {
+        match receiver.recv_timeout(Duration::from_secs(1)) {
+            Ok(received) => {
+                if !handle_payload(received, &mut mqtt, &mut spool, &mut sinks, &config) {
+                    break;
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => {
+                break;
+            }
         }
-        Err(err) => {
-            error!("Failed to create radio thermostat: {}", err)
+        supervisor.check();
+        mqtt.poll();
+        for message in mqtt.commands() {
+            //Anything wrong with the command itself is answered straight away, otherwise the sensor's thread replies
+            let result = Request::parse(&message).and_then(|request| {
+                supervisor.command(request.clone()).map_err(|err| request.reply(Err(err)))
+            });
+            if let Err(reply) = result {
+                send_reply(&mut mqtt, &config, reply);
+            }
+        }
+        if mqtt.is_connected() && last_replay.elapsed() >= REPLAY_INTERVAL {
+            if let Some(ref mut spool) = spool {
+                //After a whole batch went out MQ is clearly back, carry on next time round instead of waiting
+                if !replay(&mut mqtt, spool) {
+                    last_replay = Instant::now();
+                }
+            }
         }
     }
 
-    //let lightning_sensor = As3935::new(mpsc::Sender::clone(&sender), Arc::clone(&i2c_mutex)).unwrap();
-    //As3935::start_thread(lightning_sensor);
+    //Stopping a thread leaves its device in a safe state, wait for all of them before sending whatever they sent last
+    supervisor.join();
+    buses.log_wait_stats();
+    for received in receiver.try_iter() {
+        handle_payload(received, &mut mqtt, &mut spool, &mut sinks, &config);
+    }
+    sinks.shutdown();
 
-    //TODO Average temp/humidity values?
+    mqtt.disconnect();
+    info!("Shutdown complete");
+}
 
-    info!("Connecting to MQ");
-    let m = mosquitto_client::Mosquitto::new("indoor_sensors");
-    m.connect("localhost", 1883).unwrap();
+/// Same as the logging config, prefer the one in /etc and fall back to one local to the app
+fn config_path() -> &'static Path {
+    let etc_sensor_config = Path::new("/etc/indoor_sensors/config.yaml");
+    if let true = etc_sensor_config.exists() {
+        etc_sensor_config
+    } else {
+        Path::new("config.yaml")
+    }
+}
 
-    for received in receiver {
-        if received.queue == "poison" && received.bytes == "poison" {
-            error!("One of our threads panicked holding the I2C lock and poisoned it, killing the app");
-            panic!("One of our threads panicked holding the I2C lock and poisoned it, killing the app");
+/// Returns false once we have been told to shut down
+fn handle_payload(received: Payload, publisher: &mut dyn Publisher, spool: &mut Option<Spool>, sinks: &mut Sinks, config: &Config) -> bool {
+    match received {
+        Payload::Readings(readings) => {
+            for reading in &readings {
+                debug!("Reading from {}: {} {}{}", reading.sensor, reading.quantity.name(), reading.value, reading.quantity.unit());
+            }
+            //Handed over first, the sinks work on their own threads while we wait on MQ
+            sinks.readings(&readings);
+            let sensor = match readings.first() {
+                Some(reading) => reading.sensor,
+                None => return true,
+            };
+            match config.topic_for(sensor) {
+                Some(topic) => {
+                    for message in output::encode(config.mqtt.format_for(topic), &readings, topic, config) {
+                        info!("Sending Message to '{}' payload '{}'", message.topic, String::from_utf8_lossy(&message.body));
+                        publish(publisher, spool, &message.topic, &message.body);
+                    }
+                }
+                None => {
+                    error!("No topic is configured for {}, dropping its readings", sensor);
+                }
+            }
+            //Only the latest value matters on these, they are kept as retained messages rather than spooled
+            if config.state_topics.enabled {
+                for (topic, value) in output::state::encode(&readings, config) {
+                    publisher.set_retained(&topic, value.as_bytes());
+                }
+            }
+            if config.home_assistant.enabled {
+                if let Some((topic, body)) = output::home_assistant::encode(&readings, config) {
+                    publish(publisher, spool, &topic, body.as_bytes());
+                }
+            }
+        }
+        Payload::Event(event) => {
+            info!("Event from {}: {}", event.sensor, event.kind);
+            sinks.event(&event);
         }
+        Payload::Health(health) => {
+            sinks.health(&health);
+            let online = match health.status {
+                Status::Up => {
+                    info!("{} is up", health.sensor);
+                    true
+                }
+                Status::Failed(reason) => {
+                    warn!("{} is failing: {}", health.sensor, reason);
+                    false
+                }
+                Status::Restarted(restarts) => {
+                    warn!("{} was restarted, {} restarts so far", health.sensor, restarts);
+                    true
+                }
+                //The sensor's thread already logged it, availability only changes with Up and Failed
+                Status::Error(_) => return true,
+            };
+            let topic = config.mqtt.sensor_availability_topic(health.sensor);
+            publisher.set_retained(&topic, if online { b"online" } else { b"offline" });
+        }
+        Payload::Reply(reply) => {
+            send_reply(publisher, config, reply);
+        }
+        Payload::Control(Control::Shutdown) => {
+            info!("Shutting down");
+            return false;
+        }
+        Payload::Control(Control::Fatal(reason)) => {
+            error!("{}, refusing to carry on", reason);
+            process::exit(1);
+        }
+    }
+    true
+}
 
-        info!("Sending Message to '{}' payload '{}'", received.queue, received.bytes);
-        send_to_topic(&m, &received.queue, &received.bytes.as_bytes());
+/// Replies are only any use to whoever sent the command, they are never spooled
+fn send_reply(publisher: &mut dyn Publisher, config: &Config, reply: Reply) {
+    if reply.ok {
+        info!("{} command for {} succeeded: {}", reply.command, reply.sensor, reply.message);
+    } else {
+        warn!("{} command for {} failed: {}", reply.command, reply.sensor, reply.message);
+    }
+    match serde_json::to_string(&reply) {
+        Ok(body) => {
+            if !publisher.publish(&config.mqtt.response_topic, body.as_bytes()) {
+                error!("Failed to send the reply to the {} command for {}", reply.command, reply.sensor);
+            }
+        }
+        Err(err) => {
+            error!("Failed to serialize the reply to the {} command: {}", reply.command, err);
+        }
     }
 }
 
-fn send_to_topic(m: &Mosquitto, topic: &str, payload: &[u8]) {
-    for i in 1..6 {
-        match m.publish_wait(topic, payload, 2, false, 1000) {
-            Ok(id) => {
-                debug!("Message {} published successfully to {} after {} attempts", id, topic, i);
-                if i > 1 {
-                    info!("Message {} published successfully to {} after {} attempts", id, topic, i);
-                }
-                break;
+/// Sends a message, or spools it to be sent later if MQ can't be reached. While anything is spooled new
+/// messages go to the back of the spool so they are still sent in order.
+fn publish(publisher: &mut dyn Publisher, spool: &mut Option<Spool>, topic: &str, payload: &[u8]) {
+    match *spool {
+        Some(ref mut spool) => {
+            if spool.is_empty() && publisher.publish(topic, payload) {
+                return;
             }
-            Err(e) => {
-                debug!("Failed to enqueue data: {} to topic {}, will retry {} more times", e, topic, 5 - i);
-                if i == 5 {
-                    error!("Failed to enqueue message after 5 tries to topic {}, it will be dropped", topic);
-                    panic!("Failed to enqueue message after 5 tries to topic {}, it will be dropped", topic);
+            match spool.push(topic, payload) {
+                Ok(_) => {
+                    debug!("Spooled message to {}, {} messages are waiting", topic, spool.len());
+                }
+                Err(err) => {
+                    error!("Failed to spool message to {}: {}, it will be dropped", topic, err);
                 }
             }
+        }
+        None => {
+            if !publisher.publish(topic, payload) {
+                error!("Message to topic {} will be dropped", topic);
+            }
+        }
+    }
+}
+
+/// Sends spooled messages oldest first until the spool is empty or MQ fails again, a batch at a time so the
+/// main loop isn't held up for long after a long outage. Returns true if there may be more to send.
+fn replay(publisher: &mut dyn Publisher, spool: &mut Spool) -> bool {
+    for _ in 0..REPLAY_BATCH {
+        let message = match spool.peek() {
+            Ok(Some(message)) => message,
+            Ok(None) => return false,
+            Err(err) => {
+                error!("Failed to read from the spool: {}", err);
+                return false;
+            }
         };
+        if !publisher.publish(&message.topic, &message.payload) {
+            debug!("MQ is unavailable again, {} messages are still spooled", spool.len());
+            return false;
+        }
+        if let Err(err) = spool.pop() {
+            error!("Failed to remove sent message from the spool: {}", err);
+            return false;
+        }
+        if spool.is_empty() {
+            info!("All spooled messages have been sent");
+        }
     }
+    !spool.is_empty()
 }
@@ -0,0 +1,314 @@
+use std::cmp;
+use std::collections::{BTreeMap, VecDeque};
+use std::mem;
+use std::fs;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use rumqttc::{Client, Connection, Event, LastWill, MqttOptions, Outgoing, Packet, QoS, TlsConfiguration, Transport};
+
+use config::MqttConfig;
+use error::Error;
+use publisher::Publisher;
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+//How often the connection thread looks up from its backoff to see if we are disconnecting
+const STOP_POLL: Duration = Duration::from_millis(250);
+//How long a publish waits for the broker to finish the QoS 2 handshake, after that the client carries on with it alone
+const PUBLISH_TIMEOUT: Duration = Duration::from_secs(5);
+//Requests waiting for the connection thread, well over every retained message we re-send on a connect so queueing
+//them never has to wait
+const REQUEST_CAPACITY: usize = 1000;
+const ONLINE: &[u8] = b"online";
+const OFFLINE: &[u8] = b"offline";
+
+/// What the connection thread tells the publisher about
+enum ConnectionEvent {
+    Connected,
+    Disconnected,
+    /// The packet id a publish was sent with, publishes are sent in the order they were queued
+    Sent(u16),
+    /// The broker acknowledged a QoS 1 publish
+    Acked(u16),
+    /// The broker has completed the QoS 2 handshake for a packet id
+    Completed(u16),
+    /// A message arrived on a topic we subscribed to
+    Received(Vec<u8>),
+}
+
+/// The connection to the MQ broker using rumqttc, it's pure Rust so there are no system libraries to link.
+/// rumqttc needs its connection driven all the time, that happens on its own thread which reconnects with
+/// a backoff whenever the broker goes away, losing the broker is never fatal.
+pub struct Mqtt {
+    client: Client,
+    events: Receiver<ConnectionEvent>,
+    connected: bool,
+    stopping: Arc<AtomicBool>,
+    connection_thread: Option<JoinHandle<()>>,
+    availability_topic: String,
+    //Everything set with `set_retained`, sent again on every connect in case the broker lost it
+    retained: BTreeMap<String, Vec<u8>>,
+    //Publishes handed to the client which it hasn't sent yet, by our own sequence number, in the order it sends them
+    queued: VecDeque<u64>,
+    next_seq: u64,
+    //Packet ids which were sent and not acknowledged yet and the publish each one is. The client sends these again
+    //by itself after a reconnect, with the same packet id.
+    in_flight: BTreeMap<u16, u64>,
+    command_topic: Option<String>,
+    commands: Vec<Vec<u8>>,
+}
+
+impl Mqtt {
+    /// Sets up the client from the config and starts connecting. Only a bad config is an error,
+    /// if the broker can't be reached we carry on and the connection thread keeps trying.
+    pub fn connect(config: &MqttConfig) -> Result<Mqtt, Error> {
+        let client_id = config.client_id();
+        info!("Connecting to MQ at {}:{} as {}", config.host, config.port, client_id);
+
+        let mut options = MqttOptions::new(client_id, config.host.clone(), config.port);
+        options.set_keep_alive(Duration::from_secs(config.keepalive_secs));
+        //If we drop off without saying goodbye the broker marks us offline
+        options.set_last_will(LastWill::new(config.availability_topic.clone(), OFFLINE, QoS::AtLeastOnce, true));
+        if let Some(ref username) = config.username {
+            options.set_credentials(username.clone(), config.password.clone().unwrap_or_default());
+        }
+        if let Some(ref tls) = config.tls {
+            let client_auth = match (&tls.cert_file, &tls.key_file) {
+                (&Some(ref cert_file), &Some(ref key_file)) => Some((fs::read(cert_file)?, fs::read(key_file)?)),
+                _ => None,
+            };
+            options.set_transport(Transport::tls_with_config(TlsConfiguration::Simple {
+                ca: fs::read(&tls.ca_file)?,
+                alpn: None,
+                client_auth,
+            }));
+        }
+
+        let (client, connection) = Client::new(options, REQUEST_CAPACITY);
+        let (sender, events) = mpsc::channel();
+        let stopping = Arc::new(AtomicBool::new(false));
+        let connection_thread = {
+            let stopping = Arc::clone(&stopping);
+            thread::spawn(move || drive_connection(connection, sender, stopping))
+        };
+
+        let mut mqtt = Mqtt {
+            client,
+            events,
+            connected: false,
+            stopping,
+            connection_thread: Some(connection_thread),
+            availability_topic: config.availability_topic.clone(),
+            retained: BTreeMap::new(),
+            queued: VecDeque::new(),
+            next_seq: 0,
+            in_flight: BTreeMap::new(),
+            command_topic: config.command_topic.clone(),
+            commands: Vec::new(),
+        };
+        //The birth message, sent once we are connected
+        let availability_topic = mqtt.availability_topic.clone();
+        mqtt.set_retained(&availability_topic, ONLINE);
+        Ok(mqtt)
+    }
+
+    /// Hands a publish to the client without waiting, returns its sequence number once the client has accepted it
+    fn enqueue(&mut self, topic: &str, qos: QoS, retain: bool, payload: &[u8]) -> Result<u64, String> {
+        self.client.try_publish(topic, qos, retain, payload.to_vec()).map_err(|err| err.to_string())?;
+        let seq = self.next_seq;
+        self.next_seq = seq + 1;
+        self.queued.push_back(seq);
+        Ok(seq)
+    }
+
+    /// Whether the client still has a publish to send or is waiting on the broker for it
+    fn is_pending(&self, seq: u64) -> bool {
+        self.queued.contains(&seq) || self.in_flight.values().any(|&in_flight| in_flight == seq)
+    }
+
+    fn send_retained(&mut self, topic: &str, payload: &[u8]) {
+        if let Err(err) = self.enqueue(topic, QoS::AtLeastOnce, true, payload) {
+            error!("Failed to send retained message to {}: {}", topic, err);
+        }
+    }
+
+    fn handle_event(&mut self, event: ConnectionEvent) {
+        match event {
+            ConnectionEvent::Connected => {
+                self.connected = true;
+                let retained: Vec<(String, Vec<u8>)> = self.retained.iter().map(|(t, p)| (t.clone(), p.clone())).collect();
+                for (topic, payload) in retained {
+                    self.send_retained(&topic, &payload);
+                }
+                //The session isn't kept so the subscription has to be made again on every connect
+                if let Some(ref command_topic) = self.command_topic {
+                    match self.client.try_subscribe(command_topic.as_str(), QoS::AtLeastOnce) {
+                        Ok(_) => {
+                            info!("Listening for commands on {}", command_topic);
+                        }
+                        Err(err) => {
+                            error!("Failed to subscribe to {}: {}", command_topic, err);
+                        }
+                    }
+                }
+            }
+            ConnectionEvent::Disconnected => {
+                self.connected = false;
+            }
+            ConnectionEvent::Sent(pkid) => {
+                //A packet id which is already in flight is the client sending it again after a reconnect
+                if !self.in_flight.contains_key(&pkid) {
+                    if let Some(seq) = self.queued.pop_front() {
+                        self.in_flight.insert(pkid, seq);
+                    }
+                }
+            }
+            ConnectionEvent::Acked(pkid) | ConnectionEvent::Completed(pkid) => {
+                self.in_flight.remove(&pkid);
+            }
+            ConnectionEvent::Received(payload) => {
+                self.commands.push(payload);
+            }
+        }
+    }
+
+    /// Waits for the broker to complete the QoS 2 handshake for a publish, returns false if it hasn't by the timeout
+    /// or the connection dropped. Either way the client still has the message and keeps trying to deliver it.
+    fn wait_for(&mut self, seq: u64) -> bool {
+        let deadline = Instant::now() + PUBLISH_TIMEOUT;
+        while self.is_pending(seq) {
+            let now = Instant::now();
+            if now >= deadline || !self.connected {
+                return false;
+            }
+            match self.events.recv_timeout(deadline - now) {
+                Ok(event) => self.handle_event(event),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    self.connected = false;
+                }
+            }
+        }
+        true
+    }
+}
+
+impl Publisher for Mqtt {
+    fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    fn poll(&mut self) {
+        while let Ok(event) = self.events.try_recv() {
+            self.handle_event(event);
+        }
+    }
+
+    fn publish(&mut self, topic: &str, payload: &[u8]) -> bool {
+        self.poll();
+        if !self.connected {
+            return false;
+        }
+        //Queued exactly once, publishing it again would be a second copy and no longer exactly once
+        let seq = match self.enqueue(topic, QoS::ExactlyOnce, false, payload) {
+            Ok(seq) => seq,
+            Err(err) => {
+                error!("Failed to enqueue message to topic {}: {}", topic, err);
+                return false;
+            }
+        };
+        if self.wait_for(seq) {
+            debug!("Message published successfully to {}", topic);
+        } else {
+            warn!("The broker hasn't confirmed the message to {} yet, the MQ client will keep sending it", topic);
+        }
+        //Once the client has it the client delivers it, spooling it as well would send it twice
+        true
+    }
+
+    fn set_retained(&mut self, topic: &str, payload: &[u8]) {
+        self.retained.insert(String::from(topic), payload.to_vec());
+        if self.connected {
+            self.send_retained(topic, payload);
+        }
+    }
+
+    fn commands(&mut self) -> Vec<Vec<u8>> {
+        self.poll();
+        mem::replace(&mut self.commands, Vec::new())
+    }
+
+    fn disconnect(&mut self) {
+        //A clean disconnect doesn't trigger the will, say we're going offline ourselves
+        if self.connected {
+            let availability_topic = self.availability_topic.clone();
+            self.send_retained(&availability_topic, OFFLINE);
+        }
+        self.stopping.store(true, Ordering::SeqCst);
+        if let Err(err) = self.client.try_disconnect() {
+            error!("Failed to disconnect from MQ: {}", err);
+        }
+        if let Some(connection_thread) = self.connection_thread.take() {
+            let _ = connection_thread.join();
+        }
+    }
+}
+
+/// Runs on its own thread for as long as the client exists, iterating the connection is what sends and
+/// receives everything and reconnects after an error.
+fn drive_connection(mut connection: Connection, sender: Sender<ConnectionEvent>, stopping: Arc<AtomicBool>) {
+    let mut backoff = INITIAL_BACKOFF;
+    for notification in connection.iter() {
+        let event = match notification {
+            Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                info!("Connected to MQ");
+                backoff = INITIAL_BACKOFF;
+                ConnectionEvent::Connected
+            }
+            Ok(Event::Incoming(Packet::PubAck(puback))) => ConnectionEvent::Acked(puback.pkid),
+            Ok(Event::Incoming(Packet::PubComp(pubcomp))) => ConnectionEvent::Completed(pubcomp.pkid),
+            Ok(Event::Incoming(Packet::Publish(publish))) => ConnectionEvent::Received(publish.payload.to_vec()),
+            Ok(Event::Outgoing(Outgoing::Publish(pkid))) => ConnectionEvent::Sent(pkid),
+            Ok(Event::Outgoing(Outgoing::Disconnect)) => {
+                break;
+            }
+            Ok(_) => continue,
+            Err(err) => {
+                if stopping.load(Ordering::SeqCst) {
+                    break;
+                }
+                error!("MQ connection error: {}, reconnecting in {} seconds", err, backoff.as_secs());
+                let _ = sender.send(ConnectionEvent::Disconnected);
+                //The next iteration reconnects straight away, don't hammer a broker which is down
+                if sleep_unless_stopping(backoff, &stopping) {
+                    break;
+                }
+                backoff = cmp::min(backoff * 2, MAX_BACKOFF);
+                continue;
+            }
+        };
+        if sender.send(event).is_err() {
+            break;
+        }
+    }
+}
+
+/// Sleeps in short slices so a disconnect isn't held up by the backoff, returns true if we are disconnecting
+fn sleep_unless_stopping(duration: Duration, stopping: &AtomicBool) -> bool {
+    let deadline = Instant::now() + duration;
+    loop {
+        if stopping.load(Ordering::SeqCst) {
+            return true;
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return false;
+        }
+        thread::sleep(cmp::min(deadline - now, STOP_POLL));
+    }
+}
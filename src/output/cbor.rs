@@ -0,0 +1,36 @@
+use std::collections::BTreeMap;
+
+use config::Config;
+use output::Message;
+use payload::Reading;
+
+/// What a CBOR message holds, a map of quantity names to values keeps it readable on a microcontroller without
+/// knowing any sensor_lib ids
+#[derive(Serialize)]
+struct Aggregate<'a> {
+    sensor: &'a str,
+    location: u16,
+    /// Milliseconds since the unix epoch
+    timestamp: u64,
+    readings: BTreeMap<&'a str, f64>,
+}
+
+pub fn encode(readings: &[Reading], topic: &str, config: &Config) -> Vec<Message> {
+    let first = match readings.first() {
+        Some(first) => first,
+        None => return Vec::new(),
+    };
+    let aggregate = Aggregate {
+        sensor: first.sensor,
+        location: config.location,
+        timestamp: first.timestamp,
+        readings: readings.iter().map(|reading| (reading.quantity.name(), reading.value)).collect(),
+    };
+    match serde_cbor::to_vec(&aggregate) {
+        Ok(body) => vec![Message::new(topic, body)],
+        Err(err) => {
+            error!("Failed to serialize the {} readings to CBOR: {}", first.sensor, err);
+            Vec::new()
+        }
+    }
+}
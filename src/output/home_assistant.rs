@@ -0,0 +1,128 @@
+use serde_json::{Map, Value as JsonValue};
+
+use config::Config;
+use output::number;
+use payload::{Reading, Quantity};
+
+/// Every sensor which sends readings and the quantities it sends, an entity is announced for each of them
+const SENSORS: [(&str, &[Quantity]); 6] = [
+    ("htu21d", &[Quantity::Temperature, Quantity::Humidity]),
+    ("bmp280", &[Quantity::Pressure]),
+    ("sgp30", &[Quantity::Co2, Quantity::Tvoc]),
+    ("sds011", &[Quantity::Pm2_5, Quantity::Pm10]),
+    ("geiger", &[Quantity::Cpm]),
+    ("thermostat", &[
+        Quantity::ThermostatTemperature,
+        Quantity::ThermostatHeatSetpoint,
+        Quantity::ThermostatCoolSetpoint,
+        Quantity::ThermostatMode,
+        Quantity::ThermostatFanMode,
+        Quantity::ThermostatState,
+        Quantity::ThermostatFanState,
+        Quantity::ThermostatHold,
+        Quantity::ThermostatOverride,
+    ]),
+];
+
+/// The retained discovery documents for every quantity of every enabled sensor, returns the topic and body of each
+pub fn discovery(config: &Config) -> Vec<(String, String)> {
+    let mut messages = Vec::new();
+    for &(sensor, quantities) in SENSORS.iter() {
+        if !config.is_enabled(sensor) {
+            continue;
+        }
+        for &quantity in quantities {
+            let object_id = format!("indoor_sensors_{}_{}_{}", config.location, sensor, quantity.name());
+            let topic = format!("{}/sensor/{}/config", config.home_assistant.discovery_prefix, object_id);
+            match serde_json::to_string(&JsonValue::Object(document(config, sensor, quantity, &object_id))) {
+                Ok(body) => {
+                    messages.push((topic, body));
+                }
+                Err(err) => {
+                    error!("Failed to serialize the discovery document for {}: {}", object_id, err);
+                }
+            }
+        }
+    }
+    messages
+}
+
+/// All the readings from one aggregate as a single JSON object keyed by quantity, which is what the entities read
+pub fn encode(readings: &[Reading], config: &Config) -> Option<(String, String)> {
+    let sensor = readings.first()?.sensor;
+    let mut state = Map::new();
+    for reading in readings {
+        state.insert(String::from(reading.quantity.name()), number(reading.value));
+    }
+    match serde_json::to_string(&JsonValue::Object(state)) {
+        Ok(body) => Some((config.home_assistant.sensor_state_topic(sensor), body)),
+        Err(err) => {
+            error!("Failed to serialize the state of {}: {}", sensor, err);
+            None
+        }
+    }
+}
+
+fn document(config: &Config, sensor: &str, quantity: Quantity, object_id: &str) -> Map<String, JsonValue> {
+    let (name, device_class) = describe(quantity);
+    let mut document = Map::new();
+    document.insert(String::from("name"), JsonValue::from(name));
+    document.insert(String::from("object_id"), JsonValue::from(object_id));
+    document.insert(String::from("unique_id"), JsonValue::from(object_id));
+    document.insert(String::from("state_topic"), JsonValue::from(config.home_assistant.sensor_state_topic(sensor)));
+    document.insert(String::from("value_template"), JsonValue::from(format!("{{{{ value_json.{} }}}}", quantity.name())));
+    if let Some(device_class) = device_class {
+        document.insert(String::from("device_class"), JsonValue::from(device_class));
+    }
+    //The thermostat's modes and states are enumerations sent as numbers, they have no unit and graphing them makes no sense
+    if !quantity.unit().is_empty() {
+        document.insert(String::from("unit_of_measurement"), JsonValue::from(quantity.unit()));
+        document.insert(String::from("state_class"), JsonValue::from("measurement"));
+    }
+
+    //Unavailable if either the app or this sensor is offline
+    let availability = vec![
+        availability(&config.mqtt.availability_topic),
+        availability(&config.mqtt.sensor_availability_topic(sensor)),
+    ];
+    document.insert(String::from("availability"), JsonValue::Array(availability));
+    document.insert(String::from("availability_mode"), JsonValue::from("all"));
+
+    let mut device = Map::new();
+    device.insert(String::from("identifiers"), JsonValue::from(vec![format!("indoor_sensors_{}", config.location)]));
+    device.insert(String::from("name"), JsonValue::from(format!("Indoor Sensors {}", config.location)));
+    device.insert(String::from("model"), JsonValue::from("indoor_sensors"));
+    device.insert(String::from("sw_version"), JsonValue::from(env!("CARGO_PKG_VERSION")));
+    document.insert(String::from("device"), JsonValue::Object(device));
+    document
+}
+
+fn availability(topic: &str) -> JsonValue {
+    let mut availability = Map::new();
+    availability.insert(String::from("topic"), JsonValue::from(topic));
+    JsonValue::Object(availability)
+}
+
+/// The entity's name and Home Assistant device class, if there is one for the quantity
+fn describe(quantity: Quantity) -> (&'static str, Option<&'static str>) {
+    match quantity {
+        Quantity::Temperature => ("Temperature", Some("temperature")),
+        Quantity::Humidity => ("Humidity", Some("humidity")),
+        Quantity::Pressure => ("Pressure", Some("pressure")),
+        Quantity::Co2 => ("eCO2", Some("carbon_dioxide")),
+        Quantity::Tvoc => ("TVOC", Some("volatile_organic_compounds_parts")),
+        Quantity::Pm2_5 => ("PM2.5", Some("pm25")),
+        Quantity::Pm10 => ("PM10", Some("pm10")),
+        Quantity::Cpm => ("Radiation", None),
+        Quantity::ThermostatTemperature => ("Thermostat Temperature", Some("temperature")),
+        Quantity::ThermostatHeatSetpoint => ("Thermostat Heat Setpoint", Some("temperature")),
+        Quantity::ThermostatCoolSetpoint => ("Thermostat Cool Setpoint", Some("temperature")),
+        Quantity::ThermostatMode => ("Thermostat Mode", None),
+        Quantity::ThermostatFanMode => ("Thermostat Fan Mode", None),
+        Quantity::ThermostatState => ("Thermostat State", None),
+        Quantity::ThermostatFanState => ("Thermostat Fan State", None),
+        Quantity::ThermostatHold => ("Thermostat Hold", None),
+        Quantity::ThermostatOverride => ("Thermostat Override", None),
+    }
+}
+
@@ -0,0 +1,23 @@
+use config::Config;
+use output::{Message, format_value};
+use payload::Reading;
+
+/// InfluxDB line protocol, one line per aggregate with the sensor as the measurement, the location as a tag and a
+/// field for each quantity, e.g. `sgp30,location=2 co2=512,tvoc=31 1546300800000000000`. Every field is sent as a
+/// float so a value which happens to be whole never clashes with the field's type.
+pub fn encode(readings: &[Reading], topic: &str, config: &Config) -> Vec<Message> {
+    match line(readings, config) {
+        Some(line) => vec![Message::new(topic, line.into_bytes())],
+        None => Vec::new(),
+    }
+}
+
+/// The line for one aggregate without a trailing newline
+pub fn line(readings: &[Reading], config: &Config) -> Option<String> {
+    let first = readings.first()?;
+    let fields: Vec<String> = readings.iter()
+        .map(|reading| format!("{}={}", reading.quantity.name(), format_value(reading.value)))
+        .collect();
+    //Line protocol timestamps are in nanoseconds
+    Some(format!("{},location={} {} {}", first.sensor, config.location, fields.join(","), first.timestamp * 1_000_000))
+}
@@ -0,0 +1,93 @@
+use serde_json::{Map, Value as JsonValue};
+
+use sensor_lib::{SensorValue, TempHumidityValue, AirParticulateValue, ThermostatValue};
+
+use config::Config;
+use output::{Message, format_value, number};
+use payload::{Reading, Quantity};
+use threads::radiothermostat;
+
+/// Encodes the readings from one aggregate the way they have always been sent, as sensor_lib structs
+/// serialized to JSON.
+pub fn encode(readings: &[Reading], topic: &str, config: &Config) -> Vec<Message> {
+    let mut messages = Vec::new();
+    let timestamp = match readings.first() {
+        Some(reading) => reading.timestamp,
+        None => return messages,
+    };
+    let value_of = |quantity: Quantity| readings.iter().find(|r| r.quantity == quantity).map(|r| r.value);
+
+    if let (Some(temp), Some(humidity)) = (value_of(Quantity::Temperature), value_of(Quantity::Humidity)) {
+        let temp_humidity = TempHumidityValue {
+            timestamp,
+            location: config.location,
+            temp: temp as f32,
+            humidity: humidity as f32,
+        };
+        push(&mut messages, topic, serde_json::to_string(&temp_humidity));
+    }
+
+    if let (Some(pm2_5), Some(pm10)) = (value_of(Quantity::Pm2_5), value_of(Quantity::Pm10)) {
+        let air_part_val = AirParticulateValue {
+            timestamp,
+            location: config.location,
+            pm2_5: pm2_5 as i16,
+            pm10: pm10 as i16,
+        };
+        push(&mut messages, topic, serde_json::to_string(&air_part_val));
+    }
+
+    for reading in readings {
+        if let Some(id) = sensor_value_id(reading.quantity, config) {
+            let sensor_val = SensorValue {
+                id,
+                timestamp,
+                value: format_value(reading.value),
+            };
+            push(&mut messages, topic, serde_json::to_string(&sensor_val));
+        }
+    }
+
+    //The thermostat value is rebuilt from the same field names the thermostat itself sends
+    let mut fields = Map::new();
+    for &(key, quantity) in radiothermostat::FIELDS.iter() {
+        if let Some(value) = value_of(quantity) {
+            fields.insert(String::from(key), number(value));
+        }
+    }
+    if !fields.is_empty() {
+        fields.insert(String::from("timestamp"), JsonValue::from(timestamp));
+        match serde_json::from_value::<ThermostatValue>(JsonValue::Object(fields)) {
+            Ok(val) => {
+                push(&mut messages, topic, serde_json::to_string(&val));
+            }
+            Err(err) => {
+                error!("Failed to build the thermostat value: {}", err);
+            }
+        }
+    }
+
+    messages
+}
+
+/// The ids consumers of the generic topic use to tell the values apart
+fn sensor_value_id(quantity: Quantity, config: &Config) -> Option<u16> {
+    match quantity {
+        Quantity::Co2 => Some(config.sgp30.co2_id),
+        Quantity::Tvoc => Some(config.sgp30.tvoc_id),
+        Quantity::Pressure => Some(config.bmp280.pressure_id),
+        Quantity::Cpm => Some(config.geiger.cpm_id),
+        _ => None,
+    }
+}
+
+fn push(messages: &mut Vec<Message>, topic: &str, serialized: Result<String, serde_json::Error>) {
+    match serialized {
+        Ok(val) => {
+            messages.push(Message::new(topic, val.into_bytes()));
+        }
+        Err(err) => {
+            error!("Failed to serialize the sensor value: {}", err);
+        }
+    }
+}
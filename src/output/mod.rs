@@ -0,0 +1,65 @@
+use serde_json::{Number, Value as JsonValue};
+
+use config::{Config, Format};
+use payload::Reading;
+
+pub mod cbor;
+pub mod home_assistant;
+pub mod influx;
+pub mod json;
+pub mod plain;
+pub mod state;
+
+/// One message to send, the body is text for every format except CBOR
+pub struct Message {
+    pub topic: String,
+    pub body: Vec<u8>,
+}
+
+impl Message {
+    pub fn new(topic: &str, body: Vec<u8>) -> Message {
+        Message {
+            topic: String::from(topic),
+            body,
+        }
+    }
+}
+
+/// Encodes the readings from one aggregate in `format` as the messages to send to `topic`
+pub fn encode(format: Format, readings: &[Reading], topic: &str, config: &Config) -> Vec<Message> {
+    match format {
+        Format::Json => json::encode(readings, topic, config),
+        Format::Influx => influx::encode(readings, topic, config),
+        Format::Cbor => cbor::encode(readings, topic, config),
+        Format::Plain => plain::encode(readings, topic),
+    }
+}
+
+/// The MIME type of a message in `format`, for sinks which send over HTTP
+pub fn content_type(format: Format) -> &'static str {
+    match format {
+        Format::Json => "application/json",
+        Format::Influx => "text/plain; charset=utf-8",
+        Format::Cbor => "application/cbor",
+        Format::Plain => "text/plain; charset=utf-8",
+    }
+}
+
+/// Whole numbers are sent without a decimal point, anything else with the shortest representation of the f32
+pub fn format_value(value: f64) -> String {
+    if value.fract() == 0.0 {
+        return (value as i64).to_string();
+    }
+    let mut buf = [b'\0'; 30];
+    let len = dtoa::write(&mut buf[..], value as f32).unwrap();
+    String::from(std::str::from_utf8(&buf[..len]).unwrap())
+}
+
+/// The JSON counterpart of `format_value`, whole numbers as integers
+pub fn number(value: f64) -> JsonValue {
+    if value.fract() == 0.0 {
+        JsonValue::from(value as i64)
+    } else {
+        Number::from_f64(value).map(JsonValue::Number).unwrap_or(JsonValue::Null)
+    }
+}
@@ -0,0 +1,9 @@
+use output::{Message, format_value};
+use payload::Reading;
+
+/// Every reading on its own subtopic of `topic` named after its quantity, with nothing but the number as the body
+pub fn encode(readings: &[Reading], topic: &str) -> Vec<Message> {
+    readings.iter()
+        .map(|reading| Message::new(&format!("{}/{}", topic, reading.quantity.name()), format_value(reading.value).into_bytes()))
+        .collect()
+}
@@ -0,0 +1,11 @@
+use config::Config;
+use output::format_value;
+use payload::Reading;
+
+/// Every reading on its own topic named after its quantity, with nothing but the value as the body. These are sent
+/// retained so a new subscriber gets the latest value straight away without knowing any sensor_lib ids.
+pub fn encode(readings: &[Reading], config: &Config) -> Vec<(String, String)> {
+    readings.iter()
+        .map(|reading| (config.state_topics.topic_for(config.location, reading.quantity.name()), format_value(reading.value)))
+        .collect()
+}
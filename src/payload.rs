@@ -0,0 +1,186 @@
+use std::fmt::{Display, Formatter};
+use std::time::SystemTime;
+
+use command::Reply;
+
+/// Everything the sensor threads send to the main thread.
+pub enum Payload {
+    /// All the readings from one `aggregate` of a sensor, they share a timestamp
+    Readings(Vec<Reading>),
+    Event(Event),
+    Health(Health),
+    /// The outcome of a command sent to a sensor
+    Reply(Reply),
+    Control(Control),
+}
+
+#[derive(Debug, Clone)]
+pub struct Reading {
+    pub sensor: &'static str,
+    pub quantity: Quantity,
+    pub value: f64,
+    /// Milliseconds since the unix epoch
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Quantity {
+    Temperature,
+    Humidity,
+    Pressure,
+    Co2,
+    Tvoc,
+    Pm2_5,
+    Pm10,
+    Cpm,
+    ThermostatTemperature,
+    ThermostatHeatSetpoint,
+    ThermostatCoolSetpoint,
+    ThermostatMode,
+    ThermostatFanMode,
+    ThermostatState,
+    ThermostatFanState,
+    ThermostatHold,
+    ThermostatOverride,
+}
+
+impl Quantity {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Quantity::Temperature => "temperature",
+            Quantity::Humidity => "humidity",
+            Quantity::Pressure => "pressure",
+            Quantity::Co2 => "co2",
+            Quantity::Tvoc => "tvoc",
+            Quantity::Pm2_5 => "pm2_5",
+            Quantity::Pm10 => "pm10",
+            Quantity::Cpm => "cpm",
+            Quantity::ThermostatTemperature => "thermostat_temperature",
+            Quantity::ThermostatHeatSetpoint => "thermostat_heat_setpoint",
+            Quantity::ThermostatCoolSetpoint => "thermostat_cool_setpoint",
+            Quantity::ThermostatMode => "thermostat_mode",
+            Quantity::ThermostatFanMode => "thermostat_fan_mode",
+            Quantity::ThermostatState => "thermostat_state",
+            Quantity::ThermostatFanState => "thermostat_fan_state",
+            Quantity::ThermostatHold => "thermostat_hold",
+            Quantity::ThermostatOverride => "thermostat_override",
+        }
+    }
+
+    /// Unit the value is sent in, empty for the thermostat's enumerated modes and states
+    pub fn unit(&self) -> &'static str {
+        match *self {
+            Quantity::Temperature => "°F",
+            Quantity::Humidity => "%",
+            Quantity::Pressure => "inHg",
+            Quantity::Co2 => "ppm",
+            Quantity::Tvoc => "ppb",
+            Quantity::Pm2_5 => "µg/m³",
+            Quantity::Pm10 => "µg/m³",
+            Quantity::Cpm => "CPM",
+            Quantity::ThermostatTemperature => "°F",
+            Quantity::ThermostatHeatSetpoint => "°F",
+            Quantity::ThermostatCoolSetpoint => "°F",
+            Quantity::ThermostatMode => "",
+            Quantity::ThermostatFanMode => "",
+            Quantity::ThermostatState => "",
+            Quantity::ThermostatFanState => "",
+            Quantity::ThermostatHold => "",
+            Quantity::ThermostatOverride => "",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Event {
+    pub sensor: &'static str,
+    pub kind: EventKind,
+    pub timestamp: u64,
+}
+
+impl Event {
+    /// An event which happened just now
+    pub fn new(sensor: &'static str, kind: EventKind) -> Event {
+        Event {
+            sensor,
+            kind,
+            timestamp: SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis() as u64,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum EventKind {
+    Lightning(StormDistance),
+    Noise,
+    Disturbance,
+    /// The SDS011's fan and laser were turned on for its duty cycle
+    PoweredOn,
+    PoweredOff,
+    /// The SGP30's learned baseline was saved to disk
+    BaselineSaved { co2: u16, tvoc: u16 },
+    BaselineReset,
+}
+
+impl EventKind {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            EventKind::Lightning(_) => "lightning",
+            EventKind::Noise => "noise",
+            EventKind::Disturbance => "disturbance",
+            EventKind::PoweredOn => "powered_on",
+            EventKind::PoweredOff => "powered_off",
+            EventKind::BaselineSaved { .. } => "baseline_saved",
+            EventKind::BaselineReset => "baseline_reset",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum StormDistance {
+    Kilometers(u8),
+    OutOfRange,
+    Overhead,
+}
+
+impl Display for EventKind {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        match *self {
+            EventKind::Lightning(StormDistance::Kilometers(km)) => write!(f, "Lightning detected: {} km.", km),
+            EventKind::Lightning(StormDistance::OutOfRange) => write!(f, "Lightning detected: out of range."),
+            EventKind::Lightning(StormDistance::Overhead) => write!(f, "Lightning detected: overhead."),
+            EventKind::Noise => write!(f, "Noise detected."),
+            EventKind::Disturbance => write!(f, "Disturber detected."),
+            EventKind::PoweredOn => write!(f, "Powered on."),
+            EventKind::PoweredOff => write!(f, "Powered off."),
+            EventKind::BaselineSaved { co2, tvoc } => write!(f, "Baseline saved, CO2: {} TVOC: {}.", co2, tvoc),
+            EventKind::BaselineReset => write!(f, "Baseline reset."),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Health {
+    pub sensor: &'static str,
+    pub status: Status,
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Clone)]
+pub enum Status {
+    Up,
+    /// The sensor stopped working, only sent when it goes from working to failing
+    Failed(String),
+    /// One sample failed, sent for every failure so they can be counted
+    Error(String),
+    /// The supervisor started a fresh worker for the sensor, this is how many times it has been restarted
+    Restarted(u32),
+}
+
+#[derive(Debug, Clone)]
+pub enum Control {
+    /// Stop reading sensors and exit cleanly
+    Shutdown,
+    /// Something happened the app can't carry on from, the reason is logged before exiting
+    Fatal(String),
+}
@@ -0,0 +1,21 @@
+/// Where main sends messages, it only deals with this so the client behind it can be swapped without
+/// touching the spooling and replay logic.
+pub trait Publisher {
+    fn is_connected(&self) -> bool;
+
+    /// Called regularly from the main loop to catch up on the state of the connection
+    fn poll(&mut self);
+
+    /// Sends with QoS 2, returns false only if the client couldn't take the message, once it has it the client
+    /// keeps delivering it across reconnects and it must not be spooled as well
+    fn publish(&mut self, topic: &str, payload: &[u8]) -> bool;
+
+    /// Sets a retained value, like an availability state, which is sent again every time the connection comes back.
+    /// Only the latest value for a topic matters so this doesn't wait for delivery.
+    fn set_retained(&mut self, topic: &str, payload: &[u8]);
+
+    /// Messages which arrived on the command topic since the last call
+    fn commands(&mut self) -> Vec<Vec<u8>>;
+
+    fn disconnect(&mut self);
+}
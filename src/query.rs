@@ -0,0 +1,178 @@
+use std::cmp;
+use std::time::{Duration, SystemTime};
+
+use chrono::{DateTime, Local};
+
+use config::Config;
+use output::format_value;
+use sink::history::{self, Resolution, Row};
+
+const USAGE: &str = "Usage: indoor_sensors query --sensor <sensor or quantity> [--since <24h>] [--hourly] [--csv] [--db <path>]
+
+Prints the history kept by the history sink, e.g. `indoor_sensors query --sensor co2 --since 12h`.
+  --sensor   a sensor like sgp30 for everything it reads, or a quantity like co2
+  --since    how far back to go in s, m, h or d, 24h if left out
+  --hourly   the hourly min/avg/max instead of per minute values, used anyway once --since is past
+             the per minute retention
+  --csv      CSV for exporting instead of a table
+  --db       the database to read, defaults to the path of the first history sink in the config";
+
+struct Options {
+    sensor: String,
+    since: Duration,
+    hourly: bool,
+    csv: bool,
+    db: Option<String>,
+}
+
+/// `indoor_sensors query`, returns the exit code
+pub fn run(args: &[String], config: Option<Config>) -> i32 {
+    let options = match parse(args) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            return 2;
+        }
+    };
+
+    let history_config = config.as_ref().and_then(|config| config.history()).cloned();
+    let path = match options.db.clone().or_else(|| history_config.as_ref().map(|history| history.path.clone())) {
+        Some(path) => path,
+        None => {
+            eprintln!("No history sink is configured, add one to the config or give the database with --db");
+            return 1;
+        }
+    };
+    //The per minute values past their retention are gone, fall back to the rollups rather than print nothing
+    let raw_retention = history_config.map_or(30, |history| history.raw_retention_days) * 24 * 60 * 60;
+    let resolution = if options.hourly || options.since.as_secs() > raw_retention {
+        Resolution::Hour
+    } else {
+        Resolution::Minute
+    };
+
+    let connection = match history::open_read_only(&path) {
+        Ok(connection) => connection,
+        Err(err) => {
+            eprintln!("Failed to open the history in {}: {}", path, err);
+            return 1;
+        }
+    };
+    //A --since further back than anything could have been recorded just means everything
+    let since = history::now().checked_sub(cmp::min(options.since.as_secs(), i64::MAX as u64) as i64).unwrap_or(i64::MIN);
+    let rows = match history::query(&connection, &options.sensor, since, resolution) {
+        Ok(rows) => rows,
+        Err(err) => {
+            eprintln!("Failed to read the history from {}: {}", path, err);
+            return 1;
+        }
+    };
+    if rows.is_empty() {
+        eprintln!("Nothing was recorded for {} in that time", options.sensor);
+        return 1;
+    }
+
+    if options.csv {
+        print_csv(&rows, resolution);
+    } else {
+        print_table(&rows, resolution);
+    }
+    0
+}
+
+fn parse(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        sensor: String::new(),
+        since: Duration::from_secs(24 * 60 * 60),
+        hourly: false,
+        csv: false,
+        db: None,
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--sensor" => {
+                options.sensor = args.next().ok_or("--sensor needs a value")?.clone();
+            }
+            "--since" => {
+                let since = args.next().ok_or("--since needs a value")?;
+                options.since = parse_duration(since).ok_or_else(|| format!("--since {} isn't a duration like 30m, 24h or 7d", since))?;
+            }
+            "--hourly" => {
+                options.hourly = true;
+            }
+            "--csv" => {
+                options.csv = true;
+            }
+            "--db" => {
+                options.db = Some(args.next().ok_or("--db needs a value")?.clone());
+            }
+            "-h" | "--help" => {
+                return Err(String::from("Shows the recorded history"));
+            }
+            other => {
+                return Err(format!("Unknown option {}", other));
+            }
+        }
+    }
+    if options.sensor.is_empty() {
+        return Err(String::from("--sensor is required"));
+    }
+    Ok(options)
+}
+
+/// 90s, 30m, 24h or 7d
+fn parse_duration(value: &str) -> Option<Duration> {
+    let (split, unit) = value.char_indices().last()?;
+    let number: u64 = value[..split].parse().ok()?;
+    let multiplier = match unit {
+        's' => 1,
+        'm' => 60,
+        'h' => 60 * 60,
+        'd' => 24 * 60 * 60,
+        _ => return None,
+    };
+    number.checked_mul(multiplier).map(Duration::from_secs)
+}
+
+fn local_time(secs: i64) -> String {
+    let time = DateTime::<Local>::from(SystemTime::UNIX_EPOCH + Duration::from_secs(secs as u64));
+    time.format("%Y-%m-%d %H:%M").to_string()
+}
+
+fn print_csv(rows: &[Row], resolution: Resolution) {
+    match resolution {
+        Resolution::Minute => println!("timestamp,sensor,quantity,value,unit"),
+        Resolution::Hour => println!("timestamp,sensor,quantity,min,avg,max,unit"),
+    }
+    for row in rows {
+        match resolution {
+            Resolution::Minute => {
+                println!("{},{},{},{},{}", row.time, row.sensor, row.quantity, format_value(row.avg), row.unit);
+            }
+            Resolution::Hour => {
+                println!("{},{},{},{},{},{},{}", row.time, row.sensor, row.quantity,
+                         format_value(row.min), format_value(row.avg), format_value(row.max), row.unit);
+            }
+        }
+    }
+}
+
+fn print_table(rows: &[Row], resolution: Resolution) {
+    match resolution {
+        Resolution::Minute => println!("{:<16}  {:<16}  {:<24}  {:>10}", "time", "sensor", "quantity", "value"),
+        Resolution::Hour => println!("{:<16}  {:<16}  {:<24}  {:>10}  {:>10}  {:>10}", "time", "sensor", "quantity", "min", "avg", "max"),
+    }
+    for row in rows {
+        match resolution {
+            Resolution::Minute => {
+                println!("{:<16}  {:<16}  {:<24}  {:>10} {}", local_time(row.time), row.sensor, row.quantity,
+                         format!("{:.2}", row.avg), row.unit);
+            }
+            Resolution::Hour => {
+                println!("{:<16}  {:<16}  {:<24}  {:>10}  {:>10}  {:>10} {}", local_time(row.time), row.sensor, row.quantity,
+                         format!("{:.2}", row.min), format!("{:.2}", row.avg), format!("{:.2}", row.max), row.unit);
+            }
+        }
+    }
+}
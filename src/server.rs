@@ -0,0 +1,78 @@
+use std::io::{Error as IoError, ErrorKind};
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use tiny_http::{Header, Method, Response as HttpResponse, Server};
+
+use error::Error;
+use shutdown::Shutdown;
+
+//How often the server looks up from waiting for requests to see if we are shutting down
+const SHUTDOWN_POLL: Duration = Duration::from_secs(1);
+
+/// What a handler answers a request with
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    pub fn new(status: u16, content_type: &'static str, body: Vec<u8>) -> Response {
+        Response {
+            status,
+            content_type,
+            body,
+        }
+    }
+
+    pub fn not_found() -> Response {
+        Response::new(404, "text/plain; charset=utf-8", b"Not Found\n".to_vec())
+    }
+
+    pub fn method_not_allowed() -> Response {
+        Response::new(405, "text/plain; charset=utf-8", b"Method Not Allowed\n".to_vec())
+    }
+}
+
+/// Starts a small HTTP server on its own thread, every request is answered by `handler` with the request's
+/// method and path. It's meant for a handful of requests from the LAN, they are answered one at a time.
+pub fn start<F>(name: &'static str, listen: &str, shutdown: Shutdown, handler: F) -> Result<JoinHandle<()>, Error>
+    where F: Fn(&Method, &str) -> Response + Send + 'static {
+    let server = Server::http(listen).map_err(|err| IoError::new(ErrorKind::Other, err.to_string()))?;
+    info!("Serving {} on http://{}", name, listen);
+    Ok(thread::spawn(move || {
+        let mut failing = false;
+        while !shutdown.is_triggered() {
+            let request = match server.recv_timeout(SHUTDOWN_POLL) {
+                Ok(Some(request)) => request,
+                Ok(None) => {
+                    failing = false;
+                    continue;
+                }
+                Err(err) => {
+                    //Whatever broke the listener is likely to still be broken next time, don't spin on it
+                    if !failing {
+                        error!("Failed to receive a request for {}: {}", name, err);
+                        failing = true;
+                    }
+                    shutdown.sleep(SHUTDOWN_POLL);
+                    continue;
+                }
+            };
+            failing = false;
+            //Query strings aren't used by anything we serve
+            let path = String::from(request.url().split('?').next().unwrap_or(""));
+            let response = handler(request.method(), &path);
+            debug!("{} {} {} -> {}", name, request.method(), path, response.status);
+            let content_type = Header::from_bytes(&b"Content-Type"[..], response.content_type.as_bytes()).unwrap();
+            let http_response = HttpResponse::from_data(response.body)
+                .with_status_code(response.status)
+                .with_header(content_type);
+            if let Err(err) = request.respond(http_response) {
+                debug!("Failed to answer a request for {}: {}", name, err);
+            }
+        }
+    }))
+}
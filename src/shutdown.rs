@@ -0,0 +1,79 @@
+use std::sync::{Arc, Mutex, Condvar};
+use std::sync::mpsc::Sender;
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use signal_hook::{SIGINT, SIGTERM};
+use signal_hook::iterator::Signals;
+
+use payload::{Payload, Control};
+use sync::lock;
+
+/// Shared between main and every sensor thread so a shutdown can interrupt their sleeps
+/// instead of waiting out a 5 minute interval.
+#[derive(Clone)]
+pub struct Shutdown {
+    inner: Arc<(Mutex<bool>, Condvar)>,
+}
+
+impl Shutdown {
+    pub fn new() -> Shutdown {
+        Shutdown {
+            inner: Arc::new((Mutex::new(false), Condvar::new())),
+        }
+    }
+
+    pub fn trigger(&self) {
+        let &(ref mutex, ref condvar) = &*self.inner;
+        *lock(mutex) = true;
+        condvar.notify_all();
+    }
+
+    pub fn is_triggered(&self) -> bool {
+        let &(ref mutex, _) = &*self.inner;
+        *lock(mutex)
+    }
+
+    /// Sleeps for `duration` or until a shutdown is triggered, returns true if we are shutting down.
+    pub fn sleep(&self, duration: Duration) -> bool {
+        let &(ref mutex, ref condvar) = &*self.inner;
+        let deadline = Instant::now() + duration;
+        let mut triggered = lock(mutex);
+        while !*triggered {
+            let now = Instant::now();
+            if now >= deadline {
+                break;
+            }
+            triggered = match condvar.wait_timeout(triggered, deadline - now) {
+                Ok((triggered, _)) => triggered,
+                Err(poisoned) => poisoned.into_inner().0,
+            };
+        }
+        *triggered
+    }
+}
+
+/// Waits for SIGTERM (systemd stop) or SIGINT (ctrl-c), then tells every sensor thread to stop
+/// and the main thread to finish sending what it has. A second signal exits straight away, for when
+/// shutting down cleanly is stuck on a sensor or the broker.
+pub fn handle_signals(shutdown: Shutdown, sender: Sender<Payload>) -> Result<(), std::io::Error> {
+    let signals = Signals::new(&[SIGTERM, SIGINT])?;
+    thread::spawn(move || {
+        for signal in signals.forever() {
+            if shutdown.is_triggered() {
+                warn!("Received signal {} while shutting down, exiting now", signal);
+                process::exit(1);
+            }
+            info!("Received signal {}, shutting down, send it again to exit without waiting", signal);
+            shutdown.trigger();
+            match sender.send(Payload::Control(Control::Shutdown)) {
+                Ok(_) => {}
+                Err(err) => {
+                    error!("Failed to send shutdown message to main thread: {}", err);
+                }
+            }
+        }
+    });
+    Ok(())
+}
@@ -0,0 +1,197 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tiny_http::Method;
+
+use config::ApiSinkConfig;
+use error::Error;
+use payload::{Reading, Health, Status};
+use server::{self, Response};
+use shutdown::Shutdown;
+use sink::Sink;
+use sync::lock;
+
+/// What's known about a sensor from the health and readings it has sent
+#[derive(Serialize, Clone)]
+struct SensorState {
+    name: &'static str,
+    /// up or failed, starting until the sensor's thread has said which
+    state: &'static str,
+    /// Why it's failing, while it is
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+    /// When the state last changed, milliseconds since the unix epoch like every timestamp here
+    since: u64,
+    /// When the sensor last sent readings
+    last_success: Option<u64>,
+    last_error: Option<String>,
+    last_error_at: Option<u64>,
+    /// Failed samples since we started
+    errors: u64,
+    restarts: u32,
+}
+
+impl SensorState {
+    fn new(name: &'static str, timestamp: u64) -> SensorState {
+        SensorState {
+            name,
+            state: "starting",
+            reason: None,
+            since: timestamp,
+            last_success: None,
+            last_error: None,
+            last_error_at: None,
+            errors: 0,
+            restarts: 0,
+        }
+    }
+
+    fn set_state(&mut self, state: &'static str, reason: Option<String>, timestamp: u64) {
+        if self.state != state {
+            self.since = timestamp;
+        }
+        self.state = state;
+        self.reason = reason;
+    }
+}
+
+#[derive(Serialize)]
+struct LatestReading {
+    sensor: &'static str,
+    quantity: &'static str,
+    value: f64,
+    unit: &'static str,
+    timestamp: u64,
+}
+
+#[derive(Serialize)]
+struct Readings {
+    location: u16,
+    readings: Vec<LatestReading>,
+}
+
+#[derive(Serialize)]
+struct Sensors<'a> {
+    location: u16,
+    sensors: Vec<&'a SensorState>,
+}
+
+#[derive(Serialize)]
+struct ApiError {
+    error: String,
+}
+
+#[derive(Default)]
+struct Latest {
+    readings: BTreeMap<(&'static str, &'static str), Reading>,
+    sensors: BTreeMap<&'static str, SensorState>,
+}
+
+/// Serves the latest value of every reading and the state of every sensor as JSON, for scripts on the LAN
+/// which would rather not talk MQ:
+///   GET /api/readings
+///   GET /api/sensors
+///   GET /api/sensors/{name}
+pub struct ApiSink {
+    latest: Arc<Mutex<Latest>>,
+}
+
+impl ApiSink {
+    pub fn new(config: &ApiSinkConfig, location: u16, shutdown: Shutdown) -> Result<ApiSink, Error> {
+        let latest = Arc::new(Mutex::new(Latest::default()));
+        let handler_latest = Arc::clone(&latest);
+        server::start("api", &config.listen, shutdown, move |method, path| {
+            if !path.starts_with("/api/") {
+                return Response::not_found();
+            }
+            if *method != Method::Get {
+                return Response::method_not_allowed();
+            }
+            let latest = lock(&handler_latest);
+            let segments: Vec<&str> = path.trim_end_matches('/').split('/').skip(2).collect();
+            match segments.as_slice() {
+                ["readings"] => {
+                    let readings = latest.readings.values()
+                        .map(|reading| LatestReading {
+                            sensor: reading.sensor,
+                            quantity: reading.quantity.name(),
+                            value: reading.value,
+                            unit: reading.quantity.unit(),
+                            timestamp: reading.timestamp,
+                        })
+                        .collect();
+                    json(200, &Readings {
+                        location,
+                        readings,
+                    })
+                }
+                ["sensors"] => {
+                    json(200, &Sensors {
+                        location,
+                        sensors: latest.sensors.values().collect(),
+                    })
+                }
+                ["sensors", name] => {
+                    match latest.sensors.get(name) {
+                        Some(sensor) => json(200, sensor),
+                        None => json(404, &ApiError {
+                            error: format!("Nothing has been heard from a sensor named {}", name),
+                        }),
+                    }
+                }
+                _ => Response::not_found(),
+            }
+        })?;
+        Ok(ApiSink {
+            latest,
+        })
+    }
+}
+
+impl Sink for ApiSink {
+    fn readings(&mut self, readings: &[Reading]) -> Result<(), Error> {
+        let mut latest = lock(&self.latest);
+        for reading in readings {
+            latest.readings.insert((reading.sensor, reading.quantity.name()), reading.clone());
+            let sensor = latest.sensors.entry(reading.sensor)
+                .or_insert_with(|| SensorState::new(reading.sensor, reading.timestamp));
+            sensor.last_success = Some(reading.timestamp);
+        }
+        Ok(())
+    }
+
+    fn health(&mut self, health: &Health) -> Result<(), Error> {
+        let mut latest = lock(&self.latest);
+        let sensor = latest.sensors.entry(health.sensor)
+            .or_insert_with(|| SensorState::new(health.sensor, health.timestamp));
+        match health.status {
+            Status::Up => {
+                sensor.set_state("up", None, health.timestamp);
+            }
+            Status::Failed(ref reason) => {
+                sensor.set_state("failed", Some(reason.clone()), health.timestamp);
+            }
+            Status::Restarted(restarts) => {
+                sensor.restarts = restarts;
+                sensor.set_state("up", None, health.timestamp);
+            }
+            Status::Error(ref reason) => {
+                sensor.errors += 1;
+                sensor.last_error = Some(reason.clone());
+                sensor.last_error_at = Some(health.timestamp);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn json<T: Serialize>(status: u16, body: &T) -> Response {
+    match serde_json::to_vec(body) {
+        Ok(body) => Response::new(status, "application/json", body),
+        Err(err) => {
+            error!("Failed to serialize an API response: {}", err);
+            Response::new(500, "text/plain; charset=utf-8", b"Internal Server Error\n".to_vec())
+        }
+    }
+}
@@ -0,0 +1,277 @@
+use std::fs;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::io::prelude::*;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use chrono::NaiveDate;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+
+use config::{ArchiveFormat, ArchiveSinkConfig};
+use error::Error;
+use output::format_value;
+use payload::Reading;
+use sink::Sink;
+
+const MILLIS_PER_DAY: u64 = 24 * 60 * 60 * 1000;
+
+/// One line of a JSONL archive
+#[derive(Serialize)]
+struct Line<'a> {
+    timestamp: u64,
+    location: u16,
+    sensor: &'a str,
+    quantity: &'a str,
+    value: f64,
+    unit: &'a str,
+}
+
+/// The file being appended to and the day it's for, in days since the unix epoch
+struct Current {
+    day: u64,
+    writer: BufWriter<File>,
+}
+
+/// Appends every reading to a file per UTC day, `readings-2019-01-31.jsonl` or `.csv`. Once a day is over its file
+/// is gzipped, and files older than the retention period are deleted.
+pub struct ArchiveSink {
+    config: ArchiveSinkConfig,
+    location: u16,
+    current: Option<Current>,
+}
+
+impl ArchiveSink {
+    pub fn new(config: ArchiveSinkConfig, location: u16) -> Result<ArchiveSink, Error> {
+        fs::create_dir_all(&config.dir)?;
+        let sink = ArchiveSink {
+            config,
+            location,
+            current: None,
+        };
+        //Catch up on anything left from before a restart
+        sink.tidy(today());
+        Ok(sink)
+    }
+
+    fn extension(&self) -> &'static str {
+        match self.config.format {
+            ArchiveFormat::Jsonl => "jsonl",
+            ArchiveFormat::Csv => "csv",
+        }
+    }
+
+    fn path(&self, day: u64) -> PathBuf {
+        Path::new(&self.config.dir).join(format!("readings-{}.{}", date(day).format("%Y-%m-%d"), self.extension()))
+    }
+
+    /// The writer for `day`, moving on to a new file when the day changes
+    fn writer(&mut self, day: u64) -> Result<&mut BufWriter<File>, Error> {
+        let rolled = match self.current {
+            Some(ref current) => current.day != day,
+            None => true,
+        };
+        if rolled {
+            if let Some(mut current) = self.current.take() {
+                current.writer.flush()?;
+            }
+            let path = self.path(day);
+            //A late reading for a day which was already compressed goes in a new file which is appended to the
+            //.gz in turn, it mustn't start with a second header
+            let new_file = !path.exists() && !gz_path(&path).exists();
+            let mut writer = BufWriter::new(OpenOptions::new().create(true).append(true).open(&path)?);
+            if new_file {
+                if let ArchiveFormat::Csv = self.config.format {
+                    writer.write_all(b"timestamp,location,sensor,quantity,value,unit\n")?;
+                }
+                info!("Archiving readings to {}", path.display());
+            }
+            self.current = Some(Current {
+                day,
+                writer,
+            });
+            self.tidy(today());
+        }
+        match self.current {
+            Some(ref mut current) => Ok(&mut current.writer),
+            None => Err(Error::NotInitialized),
+        }
+    }
+
+    /// Gzips the files for days which are over and deletes the ones past the retention period
+    fn tidy(&self, today: u64) {
+        let entries = match fs::read_dir(&self.config.dir) {
+            Ok(entries) => entries,
+            Err(err) => {
+                error!("Failed to list the archive in {}: {}", self.config.dir, err);
+                return;
+            }
+        };
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let path = entry.path();
+            let day = match archive_day(&path) {
+                Some(day) => day,
+                None => continue,
+            };
+            let open_day = self.current.as_ref().map(|current| current.day);
+            if self.config.retention_days > 0 && day + self.config.retention_days <= today {
+                info!("Deleting {}, it is older than {} days", path.display(), self.config.retention_days);
+                if let Err(err) = fs::remove_file(&path) {
+                    error!("Failed to delete {}: {}", path.display(), err);
+                }
+            } else if self.config.compress && day < today && Some(day) != open_day && path.extension().map_or(false, |ext| ext != "gz") {
+                if let Err(err) = compress(&path) {
+                    error!("Failed to compress {}: {}", path.display(), err);
+                }
+            }
+        }
+    }
+}
+
+impl Sink for ArchiveSink {
+    fn readings(&mut self, readings: &[Reading]) -> Result<(), Error> {
+        let (format, location) = (self.config.format, self.location);
+        for reading in readings {
+            let writer = self.writer(reading.timestamp / MILLIS_PER_DAY)?;
+            match format {
+                ArchiveFormat::Jsonl => {
+                    let line = Line {
+                        timestamp: reading.timestamp,
+                        location,
+                        sensor: reading.sensor,
+                        quantity: reading.quantity.name(),
+                        value: reading.value,
+                        unit: reading.quantity.unit(),
+                    };
+                    serde_json::to_writer(&mut *writer, &line)?;
+                    writer.write_all(b"\n")?;
+                }
+                ArchiveFormat::Csv => {
+                    writeln!(writer, "{},{},{},{},{},{}", reading.timestamp, location, reading.sensor,
+                             reading.quantity.name(), format_value(reading.value), reading.quantity.unit())?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self, _force: bool) -> Result<(), Error> {
+        //Keep what's on disk at most a second behind, a power cut on a Pi is nothing unusual
+        if let Some(ref mut current) = self.current {
+            current.writer.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// The day an archive file is for, from its name
+fn archive_day(path: &Path) -> Option<u64> {
+    let name = path.file_name()?.to_str()?;
+    if !name.starts_with("readings-") || name.len() < 19 {
+        return None;
+    }
+    let day = NaiveDate::parse_from_str(&name[9..19], "%Y-%m-%d").ok()?;
+    let days = day.signed_duration_since(date(0)).num_days();
+    if days < 0 { None } else { Some(days as u64) }
+}
+
+/// Writes `path.gz` then removes `path`, the original is only removed once the compressed copy is complete.
+/// An existing `path.gz` is appended to rather than replaced, e.g. when readings for an old day came in after it
+/// was compressed. Concatenated gzip members read back as one file with `zcat` and the like.
+fn compress(path: &Path) -> Result<(), io::Error> {
+    let gz_path = gz_path(path);
+    {
+        let mut input = File::open(path)?;
+        let output = OpenOptions::new().create(true).append(true).open(&gz_path)?;
+        let mut encoder = GzEncoder::new(output, Compression::default());
+        io::copy(&mut input, &mut encoder)?;
+        encoder.finish()?.sync_all()?;
+    }
+    fs::remove_file(path)?;
+    debug!("Compressed {}", path.display());
+    Ok(())
+}
+
+fn gz_path(path: &Path) -> PathBuf {
+    let mut gz_name = path.as_os_str().to_owned();
+    gz_name.push(".gz");
+    PathBuf::from(gz_name)
+}
+
+fn date(day: u64) -> NaiveDate {
+    //Day 0 is 1970-01-01, which is day 719163 counting from the start of the common era
+    NaiveDate::from_num_days_from_ce_opt(719163 + day as i32).unwrap()
+}
+
+fn today() -> u64 {
+    SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis() as u64 / MILLIS_PER_DAY
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    use flate2::read::MultiGzDecoder;
+    use payload::Quantity;
+
+    /// A fresh archive directory for each test, removed again when dropped
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> TempDir {
+            let dir = env::temp_dir().join(format!("indoor_sensors-archive-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            TempDir(dir)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn co2(value: f64, day: u64) -> Reading {
+        Reading {
+            sensor: "sgp30",
+            quantity: Quantity::Co2,
+            value,
+            timestamp: day * MILLIS_PER_DAY + 1000,
+        }
+    }
+
+    #[test]
+    fn late_readings_for_a_compressed_day_are_appended_without_a_header() {
+        let dir = TempDir::new("late");
+        let config = ArchiveSinkConfig {
+            dir: dir.0.to_string_lossy().into_owned(),
+            format: ArchiveFormat::Csv,
+            retention_days: 0,
+            compress: true,
+        };
+        let mut sink = ArchiveSink::new(config, 2).unwrap();
+        let (yesterday, today) = (today() - 1, today());
+
+        sink.readings(&[co2(400.0, yesterday)]).unwrap();
+        //Moving on to today compresses yesterday
+        sink.readings(&[co2(410.0, today)]).unwrap();
+        let plain = sink.path(yesterday);
+        assert!(!plain.exists());
+        assert!(gz_path(&plain).exists());
+
+        //A late reading reopens yesterday, then gets compressed onto the end of the .gz
+        sink.readings(&[co2(420.0, yesterday)]).unwrap();
+        sink.readings(&[co2(430.0, today)]).unwrap();
+        assert!(!plain.exists());
+
+        let mut csv = String::new();
+        MultiGzDecoder::new(File::open(gz_path(&plain)).unwrap()).read_to_string(&mut csv).unwrap();
+        let timestamp = yesterday * MILLIS_PER_DAY + 1000;
+        assert_eq!(csv, format!("timestamp,location,sensor,quantity,value,unit\n\
+                                 {0},2,sgp30,co2,400,ppm\n\
+                                 {0},2,sgp30,co2,420,ppm\n", timestamp));
+    }
+}
@@ -0,0 +1,294 @@
+use std::io::{Error as IoError, ErrorKind};
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime};
+
+use rusqlite::{Connection, OpenFlags, NO_PARAMS};
+
+use config::HistorySinkConfig;
+use error::Error;
+use payload::Reading;
+use sink::Sink;
+
+//How often old rows are deleted
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS minutes (
+        sensor TEXT NOT NULL,
+        quantity TEXT NOT NULL,
+        minute INTEGER NOT NULL,
+        value REAL NOT NULL,
+        samples INTEGER NOT NULL,
+        PRIMARY KEY (sensor, quantity, minute)
+    ) WITHOUT ROWID;
+    CREATE TABLE IF NOT EXISTS hours (
+        sensor TEXT NOT NULL,
+        quantity TEXT NOT NULL,
+        hour INTEGER NOT NULL,
+        min_value REAL NOT NULL,
+        avg_value REAL NOT NULL,
+        max_value REAL NOT NULL,
+        samples INTEGER NOT NULL,
+        PRIMARY KEY (sensor, quantity, hour)
+    ) WITHOUT ROWID;
+    CREATE TABLE IF NOT EXISTS units (
+        sensor TEXT NOT NULL,
+        quantity TEXT NOT NULL,
+        unit TEXT NOT NULL,
+        PRIMARY KEY (sensor, quantity)
+    ) WITHOUT ROWID;
+";
+
+/// Which table a query reads from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Resolution {
+    Minute,
+    Hour,
+}
+
+/// One row of history, times are unix seconds at the start of the minute or hour. A minute only has its
+/// average so all three values are the same.
+pub struct Row {
+    pub sensor: String,
+    pub quantity: String,
+    pub unit: String,
+    pub time: i64,
+    pub min: f64,
+    pub avg: f64,
+    pub max: f64,
+}
+
+/// Stores readings in SQLite so the history can be looked at on the Pi itself. Several readings in the same
+/// minute are averaged, and every reading also goes into an hourly min/avg/max rollup which is kept for longer.
+pub struct HistorySink {
+    config: HistorySinkConfig,
+    connection: Connection,
+    last_prune: Option<Instant>,
+}
+
+impl HistorySink {
+    pub fn new(config: HistorySinkConfig) -> Result<HistorySink, Error> {
+        let connection = open(&config.path)?;
+        info!("Keeping history in {}", config.path);
+        Ok(HistorySink {
+            config,
+            connection,
+            last_prune: None,
+        })
+    }
+
+    fn prune(&self) -> Result<(), Error> {
+        let now = now();
+        let minutes = self.connection.execute("DELETE FROM minutes WHERE minute < ?1",
+                                              &[now - days(self.config.raw_retention_days)])?;
+        let hours = if self.config.rollup_retention_days > 0 {
+            self.connection.execute("DELETE FROM hours WHERE hour < ?1", &[now - days(self.config.rollup_retention_days)])?
+        } else {
+            0
+        };
+        if minutes > 0 || hours > 0 {
+            debug!("Pruned {} minutes and {} hours from the history", minutes, hours);
+        }
+        Ok(())
+    }
+}
+
+impl Sink for HistorySink {
+    fn readings(&mut self, readings: &[Reading]) -> Result<(), Error> {
+        //All or nothing, a failed write is rolled back so the retry doesn't count anything twice
+        let transaction = self.connection.transaction()?;
+        for reading in readings {
+            let secs = (reading.timestamp / 1000) as i64;
+            let (sensor, quantity) = (reading.sensor, reading.quantity.name());
+            transaction.prepare_cached(
+                "INSERT INTO minutes (sensor, quantity, minute, value, samples) VALUES (?1, ?2, ?3, ?4, 1)
+                 ON CONFLICT (sensor, quantity, minute) DO UPDATE SET
+                     value = (value * samples + excluded.value) / (samples + 1),
+                     samples = samples + 1")?
+                .execute(params![sensor, quantity, secs - secs % 60, reading.value])?;
+            transaction.prepare_cached(
+                "INSERT INTO hours (sensor, quantity, hour, min_value, avg_value, max_value, samples) VALUES (?1, ?2, ?3, ?4, ?4, ?4, 1)
+                 ON CONFLICT (sensor, quantity, hour) DO UPDATE SET
+                     min_value = min(min_value, excluded.min_value),
+                     avg_value = (avg_value * samples + excluded.avg_value) / (samples + 1),
+                     max_value = max(max_value, excluded.max_value),
+                     samples = samples + 1")?
+                .execute(params![sensor, quantity, secs - secs % 3600, reading.value])?;
+            transaction.prepare_cached("INSERT OR REPLACE INTO units (sensor, quantity, unit) VALUES (?1, ?2, ?3)")?
+                .execute(params![sensor, quantity, reading.quantity.unit()])?;
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    fn flush(&mut self, _force: bool) -> Result<(), Error> {
+        let due = match self.last_prune {
+            Some(last_prune) => last_prune.elapsed() >= PRUNE_INTERVAL,
+            None => true,
+        };
+        if due {
+            self.prune()?;
+            self.last_prune = Some(Instant::now());
+        }
+        Ok(())
+    }
+}
+
+/// Opens the database, creating it and its tables if they aren't there yet
+pub fn open(path: &str) -> Result<Connection, Error> {
+    let connection = Connection::open(path)?;
+    //WAL lets `indoor_sensors query` read while readings are being written
+    connection.query_row("PRAGMA journal_mode = WAL", NO_PARAMS, |_| Ok(()))?;
+    connection.busy_timeout(Duration::from_secs(5))?;
+    connection.execute_batch(SCHEMA)?;
+    Ok(connection)
+}
+
+/// Opens an existing database for reading only, for `indoor_sensors query`. Nothing is created or changed,
+/// a mistyped path is an error instead of a new empty database.
+pub fn open_read_only(path: &str) -> Result<Connection, Error> {
+    if !Path::new(path).exists() {
+        return Err(Error::from(IoError::new(ErrorKind::NotFound, "there is no history database there")));
+    }
+    let connection = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    connection.busy_timeout(Duration::from_secs(5))?;
+    Ok(connection)
+}
+
+/// Everything since `since` (unix seconds) for a sensor or a quantity, whichever `name` is, oldest first
+pub fn query(connection: &Connection, name: &str, since: i64, resolution: Resolution) -> Result<Vec<Row>, Error> {
+    let sql = match resolution {
+        Resolution::Minute => {
+            "SELECT m.sensor, m.quantity, coalesce(u.unit, ''), m.minute, m.value, m.value, m.value
+             FROM minutes m LEFT JOIN units u ON u.sensor = m.sensor AND u.quantity = m.quantity
+             WHERE (m.sensor = ?1 OR m.quantity = ?1) AND m.minute >= ?2
+             ORDER BY m.minute, m.sensor, m.quantity"
+        }
+        Resolution::Hour => {
+            "SELECT h.sensor, h.quantity, coalesce(u.unit, ''), h.hour, h.min_value, h.avg_value, h.max_value
+             FROM hours h LEFT JOIN units u ON u.sensor = h.sensor AND u.quantity = h.quantity
+             WHERE (h.sensor = ?1 OR h.quantity = ?1) AND h.hour >= ?2
+             ORDER BY h.hour, h.sensor, h.quantity"
+        }
+    };
+    let mut statement = connection.prepare(sql)?;
+    let rows = statement.query_map(params![name, since], |row| {
+        Ok(Row {
+            sensor: row.get(0)?,
+            quantity: row.get(1)?,
+            unit: row.get(2)?,
+            time: row.get(3)?,
+            min: row.get(4)?,
+            avg: row.get(5)?,
+            max: row.get(6)?,
+        })
+    })?;
+    let mut result = Vec::new();
+    for row in rows {
+        result.push(row?);
+    }
+    Ok(result)
+}
+
+/// Unix seconds
+pub fn now() -> i64 {
+    SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs() as i64
+}
+
+fn days(days: u64) -> i64 {
+    days as i64 * 24 * 60 * 60
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use payload::Quantity;
+
+    //2019-01-01 00:00:00 UTC, on an hour boundary
+    const HOUR: i64 = 1_546_300_800;
+
+    fn sink() -> HistorySink {
+        let connection = Connection::open_in_memory().unwrap();
+        connection.execute_batch(SCHEMA).unwrap();
+        HistorySink {
+            config: HistorySinkConfig::default(),
+            connection,
+            last_prune: None,
+        }
+    }
+
+    fn reading(sensor: &'static str, quantity: Quantity, value: f64, secs: i64) -> Reading {
+        Reading {
+            sensor,
+            quantity,
+            value,
+            timestamp: secs as u64 * 1000,
+        }
+    }
+
+    fn values(rows: &[Row]) -> Vec<(i64, f64, f64, f64)> {
+        rows.iter().map(|row| (row.time, row.min, row.avg, row.max)).collect()
+    }
+
+    #[test]
+    fn averages_minutes_and_rolls_up_hours() {
+        let mut sink = sink();
+        sink.readings(&[reading("sgp30", Quantity::Co2, 400.0, HOUR - 50)]).unwrap();
+        sink.readings(&[reading("sgp30", Quantity::Co2, 410.0, HOUR + 5),
+                        reading("htu21d", Quantity::Temperature, 70.0, HOUR + 5)]).unwrap();
+        sink.readings(&[reading("sgp30", Quantity::Co2, 430.0, HOUR + 35)]).unwrap();
+        sink.readings(&[reading("sgp30", Quantity::Co2, 500.0, HOUR + 65)]).unwrap();
+
+        let minutes = query(&sink.connection, "co2", 0, Resolution::Minute).unwrap();
+        assert_eq!(values(&minutes), vec![
+            (HOUR - 60, 400.0, 400.0, 400.0),
+            (HOUR, 420.0, 420.0, 420.0),
+            (HOUR + 60, 500.0, 500.0, 500.0),
+        ]);
+        assert!(minutes.iter().all(|row| row.sensor == "sgp30" && row.quantity == "co2" && row.unit == "ppm"));
+
+        let hours = query(&sink.connection, "sgp30", 0, Resolution::Hour).unwrap();
+        assert_eq!(hours.len(), 2);
+        assert_eq!(values(&hours[..1]), vec![(HOUR - 3600, 400.0, 400.0, 400.0)]);
+        assert_eq!((hours[1].time, hours[1].min, hours[1].max), (HOUR, 410.0, 500.0));
+        assert!((hours[1].avg - 1340.0 / 3.0).abs() < 1e-9);
+
+        //`since` is inclusive of the minute or hour it lands on
+        let since = query(&sink.connection, "co2", HOUR, Resolution::Minute).unwrap();
+        assert_eq!(since.iter().map(|row| row.time).collect::<Vec<_>>(), vec![HOUR, HOUR + 60]);
+
+        let temperature = query(&sink.connection, "htu21d", 0, Resolution::Minute).unwrap();
+        assert_eq!(values(&temperature), vec![(HOUR, 70.0, 70.0, 70.0)]);
+    }
+
+    #[test]
+    fn prunes_minutes_before_hours() {
+        let mut sink = sink();
+        let old = now() - days(40);
+        sink.readings(&[reading("sgp30", Quantity::Co2, 400.0, old),
+                        reading("sgp30", Quantity::Co2, 410.0, now())]).unwrap();
+        sink.flush(false).unwrap();
+
+        let minutes = query(&sink.connection, "co2", 0, Resolution::Minute).unwrap();
+        assert_eq!(minutes.len(), 1);
+        assert_eq!(minutes[0].avg, 410.0);
+        assert_eq!(query(&sink.connection, "co2", 0, Resolution::Hour).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn read_only_open_never_creates_a_database() {
+        let path = std::env::temp_dir().join(format!("indoor_sensors-history-{}.db", std::process::id()));
+        let path = path.to_str().unwrap();
+        assert!(open_read_only(path).is_err());
+        assert!(!Path::new(path).exists());
+
+        drop(open(path).unwrap());
+        let connection = open_read_only(path).unwrap();
+        assert!(query(&connection, "co2", 0, Resolution::Minute).unwrap().is_empty());
+        assert!(connection.execute("DELETE FROM minutes", NO_PARAMS).is_err());
+        drop(connection);
+        for suffix in &["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", path, suffix));
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use std::io::prelude::*;
+
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use mio_httpc::CallBuilder;
+
+use config::{Config, HttpSinkConfig};
+use error::Error;
+use output;
+use payload::Reading;
+use sink::Sink;
+
+/// POSTs every message the readings encode to, one request each
+pub struct HttpSink {
+    config: HttpSinkConfig,
+    app_config: Config,
+}
+
+impl HttpSink {
+    pub fn new(config: HttpSinkConfig, app_config: Config) -> HttpSink {
+        HttpSink {
+            config,
+            app_config,
+        }
+    }
+}
+
+impl Sink for HttpSink {
+    fn readings(&mut self, readings: &[Reading]) -> Result<(), Error> {
+        let topic = match readings.first() {
+            Some(reading) => self.app_config.topic_for(reading.sensor).unwrap_or(""),
+            None => return Ok(()),
+        };
+        for message in output::encode(self.config.format, readings, topic, &self.app_config) {
+            let headers = [("Content-Type", output::content_type(self.config.format)), ("X-Topic", message.topic.as_str())];
+            let status = post(&self.config.url, message.body, &headers, false, self.config.timeout_ms)?;
+            if status < 200 || status >= 300 {
+                return Err(Error::Protocol(format!("{} answered with HTTP {}", self.config.url, status)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// POSTs a body, gzipped if asked to, and returns the HTTP status. Shared by every sink which sends over HTTP.
+pub fn post(url: &str, body: Vec<u8>, headers: &[(&str, &str)], gzip: bool, timeout_ms: u64) -> Result<u16, Error> {
+    let body = if gzip {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&body)?;
+        encoder.finish()?
+    } else {
+        body
+    };
+
+    let mut call = CallBuilder::post(body);
+    call.timeout_ms(timeout_ms);
+    for &(key, value) in headers {
+        call.header(key, value);
+    }
+    if gzip {
+        call.header("Content-Encoding", "gzip");
+    }
+    let (response, _body) = call.url(url)?.exec()?;
+    Ok(response.status)
+}
@@ -0,0 +1,198 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use config::{Config, InfluxSinkConfig};
+use error::Error;
+use output::influx;
+use payload::Reading;
+use sink::Sink;
+use sink::http;
+
+/// Writes readings to an InfluxDB `/api/v2/write` or v1 `/write` endpoint as line protocol. Lines are batched
+/// and sent once `batch_size` are waiting or `flush_interval_secs` has passed, a failed write is kept and tried
+/// again with the sink's backoff.
+pub struct InfluxSink {
+    config: InfluxSinkConfig,
+    app_config: Config,
+    batch: VecDeque<String>,
+    last_flush: Instant,
+    //Lines dropped since the buffer filled up, zero while writes are going through
+    dropped: u64,
+}
+
+impl InfluxSink {
+    pub fn new(config: InfluxSinkConfig, app_config: Config) -> InfluxSink {
+        InfluxSink {
+            config,
+            app_config,
+            batch: VecDeque::new(),
+            last_flush: Instant::now(),
+            dropped: 0,
+        }
+    }
+
+    /// One write of up to `batch_size` lines, returns the HTTP status
+    fn write(&self, lines: &[String]) -> Result<u16, Error> {
+        let mut body = lines.join("\n");
+        body.push('\n');
+        let authorization = self.config.token.as_ref().map(|token| format!("Token {}", token));
+        let mut headers = vec![("Content-Type", "text/plain; charset=utf-8")];
+        if let Some(ref authorization) = authorization {
+            headers.push(("Authorization", authorization.as_str()));
+        }
+        http::post(&self.config.url, body.into_bytes(), &headers, self.config.gzip, self.config.timeout_ms)
+    }
+}
+
+impl Sink for InfluxSink {
+    fn readings(&mut self, readings: &[Reading]) -> Result<(), Error> {
+        if let Some(line) = influx::line(readings, &self.app_config) {
+            //While InfluxDB is down the oldest lines go first
+            if self.batch.len() >= self.config.max_buffered_lines {
+                if self.dropped == 0 {
+                    warn!("InfluxDB write buffer is full with {} lines, dropping the oldest", self.batch.len());
+                }
+                self.dropped += 1;
+                self.batch.pop_front();
+            }
+            //The interval counts from the oldest waiting line, not from whenever the batch was last empty
+            if self.batch.is_empty() {
+                self.last_flush = Instant::now();
+            }
+            self.batch.push_back(line);
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self, force: bool) -> Result<(), Error> {
+        let due = force
+            || self.batch.len() >= self.config.batch_size
+            || self.last_flush.elapsed() >= Duration::from_secs(self.config.flush_interval_secs);
+        if !due || self.batch.is_empty() {
+            return Ok(());
+        }
+
+        while !self.batch.is_empty() {
+            let count = self.batch.len().min(self.config.batch_size);
+            let lines: Vec<String> = self.batch.iter().take(count).cloned().collect();
+            let status = self.write(&lines)?;
+            match status {
+                200..=299 => {
+                    debug!("Wrote {} lines to InfluxDB", count);
+                }
+                //Rejected data will be rejected again, only a server which is busy or broken is worth retrying
+                400..=499 if status != 429 => {
+                    error!("InfluxDB rejected {} lines with HTTP {}, dropping them", count, status);
+                }
+                _ => {
+                    return Err(Error::Protocol(format!("InfluxDB answered with HTTP {}", status)));
+                }
+            }
+            self.batch.drain(..count);
+        }
+
+        if self.dropped > 0 {
+            info!("InfluxDB writes are going through again, {} lines were dropped", self.dropped);
+            self.dropped = 0;
+        }
+        self.last_flush = Instant::now();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use std::sync::mpsc::{self, Receiver};
+    use std::thread;
+
+    use flate2::read::GzDecoder;
+    use payload::Quantity;
+    use tiny_http::{Response, Server};
+
+    /// What the stub server was sent
+    struct Request {
+        content_encoding: Option<String>,
+        body: Vec<u8>,
+    }
+
+    /// Answers one request with each of `statuses` in turn and hands back what it was sent
+    fn stub_server(statuses: Vec<u16>) -> (String, Receiver<Request>) {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/write?db=sensors", server.server_addr());
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for status in statuses {
+                let mut request = server.recv().unwrap();
+                let content_encoding = request.headers().iter()
+                    .find(|header| header.field.equiv("Content-Encoding"))
+                    .map(|header| header.value.to_string());
+                let mut body = Vec::new();
+                request.as_reader().read_to_end(&mut body).unwrap();
+                sender.send(Request {
+                    content_encoding,
+                    body,
+                }).unwrap();
+                request.respond(Response::empty(status)).unwrap();
+            }
+        });
+        (url, receiver)
+    }
+
+    fn sink(url: String) -> InfluxSink {
+        let config = InfluxSinkConfig {
+            url,
+            batch_size: 2,
+            ..InfluxSinkConfig::default()
+        };
+        InfluxSink::new(config, Config::default())
+    }
+
+    fn co2(value: f64, timestamp: u64) -> Vec<Reading> {
+        vec![Reading {
+            sensor: "sgp30",
+            quantity: Quantity::Co2,
+            value,
+            timestamp,
+        }]
+    }
+
+    fn gunzip(request: &Request) -> String {
+        assert_eq!(request.content_encoding.as_deref(), Some("gzip"));
+        let mut body = String::new();
+        GzDecoder::new(&request.body[..]).read_to_string(&mut body).unwrap();
+        body
+    }
+
+    #[test]
+    fn batches_gzipped_lines_and_retries_after_a_server_error() {
+        let (url, requests) = stub_server(vec![500, 204, 204]);
+        let mut sink = sink(url);
+        sink.readings(&co2(400.0, 1000)).unwrap();
+        sink.readings(&co2(410.0, 2000)).unwrap();
+        sink.readings(&co2(420.0, 3000)).unwrap();
+
+        assert!(sink.flush(true).is_err());
+        assert_eq!(sink.batch.len(), 3);
+        let failed = gunzip(&requests.recv().unwrap());
+        assert_eq!(failed, "sgp30,location=2 co2=400 1000000000\nsgp30,location=2 co2=410 2000000000\n");
+
+        sink.flush(true).unwrap();
+        assert!(sink.batch.is_empty());
+        assert_eq!(gunzip(&requests.recv().unwrap()), failed);
+        assert_eq!(gunzip(&requests.recv().unwrap()), "sgp30,location=2 co2=420 3000000000\n");
+    }
+
+    #[test]
+    fn the_interval_starts_with_the_first_line() {
+        //Nothing listens here, a write would fail
+        let mut sink = sink(String::from("http://127.0.0.1:9/write"));
+        if let Some(idle) = Instant::now().checked_sub(Duration::from_secs(60)) {
+            sink.last_flush = idle;
+        }
+        sink.readings(&co2(400.0, 1000)).unwrap();
+        sink.flush(false).unwrap();
+        assert_eq!(sink.batch.len(), 1);
+    }
+}
@@ -0,0 +1,188 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use config::{self, LokiSinkConfig};
+use error::Error;
+use output::format_value;
+use payload::{Reading, Event, Health, Status};
+use sink::Sink;
+use sink::http;
+
+/// One log line waiting to be pushed
+struct Entry {
+    sensor: &'static str,
+    kind: &'static str,
+    /// Milliseconds since the unix epoch
+    timestamp: u64,
+    line: String,
+}
+
+/// The body of a push, entries are grouped into a stream per label set
+#[derive(Serialize)]
+struct Push<'a> {
+    streams: Vec<Stream<'a>>,
+}
+
+#[derive(Serialize)]
+struct Stream<'a> {
+    stream: BTreeMap<&'static str, &'a str>,
+    /// Nanosecond timestamp as a string and the line
+    values: Vec<(String, &'a str)>,
+}
+
+/// Pushes events, sensor health and optionally readings to Loki, labelled with the host, location, sensor and kind
+/// of entry so they can be queried next to the readings. Batched the same way as the InfluxDB sink.
+pub struct LokiSink {
+    config: LokiSinkConfig,
+    host: String,
+    location: String,
+    batch: VecDeque<Entry>,
+    last_flush: Instant,
+    //Entries dropped since the buffer filled up, zero while pushes are going through
+    dropped: u64,
+}
+
+impl LokiSink {
+    pub fn new(config: LokiSinkConfig, location: u16) -> LokiSink {
+        let host = config.host.clone().or_else(config::hostname).unwrap_or_else(|| String::from("unknown"));
+        LokiSink {
+            config,
+            host,
+            location: location.to_string(),
+            batch: VecDeque::new(),
+            last_flush: Instant::now(),
+            dropped: 0,
+        }
+    }
+
+    fn add(&mut self, entry: Entry) {
+        if self.batch.len() >= self.config.max_buffered_entries {
+            if self.dropped == 0 {
+                warn!("Loki push buffer is full with {} entries, dropping the oldest", self.batch.len());
+            }
+            self.dropped += 1;
+            self.batch.pop_front();
+        }
+        //Like the InfluxDB sink, the interval counts from the oldest waiting entry
+        if self.batch.is_empty() {
+            self.last_flush = Instant::now();
+        }
+        self.batch.push_back(entry);
+    }
+
+    /// One push of up to `batch_size` entries, returns the HTTP status
+    fn push(&self, count: usize) -> Result<u16, Error> {
+        let mut streams: BTreeMap<(&str, &str), Vec<(String, &str)>> = BTreeMap::new();
+        for entry in self.batch.iter().take(count) {
+            streams.entry((entry.sensor, entry.kind))
+                .or_insert_with(Vec::new)
+                .push(((entry.timestamp * 1_000_000).to_string(), entry.line.as_str()));
+        }
+        let push = Push {
+            streams: streams.into_iter()
+                .map(|((sensor, kind), values)| {
+                    let mut labels = BTreeMap::new();
+                    labels.insert("host", self.host.as_str());
+                    labels.insert("location", self.location.as_str());
+                    labels.insert("sensor", sensor);
+                    labels.insert("kind", kind);
+                    Stream {
+                        stream: labels,
+                        values,
+                    }
+                })
+                .collect(),
+        };
+        let body = serde_json::to_vec(&push)?;
+
+        let mut headers = vec![("Content-Type", "application/json")];
+        if let Some(ref tenant) = self.config.tenant {
+            headers.push(("X-Scope-OrgID", tenant.as_str()));
+        }
+        http::post(&self.config.url, body, &headers, self.config.gzip, self.config.timeout_ms)
+    }
+}
+
+impl Sink for LokiSink {
+    fn readings(&mut self, readings: &[Reading]) -> Result<(), Error> {
+        if !self.config.readings {
+            return Ok(());
+        }
+        let first = match readings.first() {
+            Some(first) => first,
+            None => return Ok(()),
+        };
+        //logfmt so Loki's parser can pull the values back out
+        let line: Vec<String> = readings.iter()
+            .map(|reading| format!("{}={}", reading.quantity.name(), format_value(reading.value)))
+            .collect();
+        self.add(Entry {
+            sensor: first.sensor,
+            kind: "reading",
+            timestamp: first.timestamp,
+            line: line.join(" "),
+        });
+        Ok(())
+    }
+
+    fn event(&mut self, event: &Event) -> Result<(), Error> {
+        self.add(Entry {
+            sensor: event.sensor,
+            kind: event.kind.name(),
+            timestamp: event.timestamp,
+            line: event.kind.to_string(),
+        });
+        Ok(())
+    }
+
+    fn health(&mut self, health: &Health) -> Result<(), Error> {
+        let line = match health.status {
+            Status::Up => String::from("up"),
+            Status::Failed(ref reason) => format!("failed: {}", reason),
+            Status::Restarted(restarts) => format!("restarted, {} restarts so far", restarts),
+            //Every failed sample would flood the stream, the change to failing above is what's worth a line
+            Status::Error(_) => return Ok(()),
+        };
+        self.add(Entry {
+            sensor: health.sensor,
+            kind: "health",
+            timestamp: health.timestamp,
+            line,
+        });
+        Ok(())
+    }
+
+    fn flush(&mut self, force: bool) -> Result<(), Error> {
+        let due = force
+            || self.batch.len() >= self.config.batch_size
+            || self.last_flush.elapsed() >= Duration::from_secs(self.config.flush_interval_secs);
+        if !due || self.batch.is_empty() {
+            return Ok(());
+        }
+
+        while !self.batch.is_empty() {
+            let count = self.batch.len().min(self.config.batch_size);
+            let status = self.push(count)?;
+            match status {
+                200..=299 => {
+                    debug!("Pushed {} entries to Loki", count);
+                }
+                //Entries Loki rejected (too old, out of order) will be rejected again, don't hold everything else up retrying them
+                400..=499 if status != 429 => {
+                    error!("Loki rejected {} entries with HTTP {}, dropping them", count, status);
+                }
+                _ => {
+                    return Err(Error::Protocol(format!("Loki answered with HTTP {}", status)));
+                }
+            }
+            self.batch.drain(..count);
+        }
+
+        if self.dropped > 0 {
+            info!("Loki pushes are going through again, {} entries were dropped", self.dropped);
+            self.dropped = 0;
+        }
+        self.last_flush = Instant::now();
+        Ok(())
+    }
+}
@@ -0,0 +1,256 @@
+use std::cmp;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, SyncSender, TrySendError};
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use config::{Config, SinkConfig, SinkKind};
+use error::Error;
+use payload::{Reading, Event, Health};
+use shutdown::Shutdown;
+
+pub mod api;
+pub mod archive;
+pub mod history;
+pub mod http;
+pub mod influx;
+pub mod loki;
+pub mod prometheus;
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+//How often a sink is flushed when nothing is arriving
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Somewhere besides MQ that readings are delivered to. Every sink runs on its own thread so it can block or be
+/// slow without holding up anything else, an error from any of these means the same thing is tried again later.
+pub trait Sink: Send {
+    /// Deliver the readings from one aggregate, already filtered down to what this sink wants
+    fn readings(&mut self, readings: &[Reading]) -> Result<(), Error>;
+
+    fn event(&mut self, _event: &Event) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn health(&mut self, _health: &Health) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called about once a second and when stopping, sinks which batch send what they have once it's due or `force` is set
+    fn flush(&mut self, _force: bool) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// What is queued for a sink's thread
+enum Item {
+    Readings(Vec<Reading>),
+    Event(Event),
+    Health(Health),
+}
+
+struct Handle {
+    name: String,
+    sensors: Vec<String>,
+    quantities: Vec<String>,
+    sender: Option<SyncSender<Item>>,
+    thread: Option<JoinHandle<()>>,
+    //Deliveries dropped since the queue filled up, zero while it's keeping up
+    dropped: u64,
+}
+
+impl Handle {
+    fn wants_sensor(&self, sensor: &str) -> bool {
+        self.sensors.is_empty() || self.sensors.iter().any(|s| s == sensor)
+    }
+
+    fn wants(&self, reading: &Reading) -> bool {
+        self.wants_sensor(reading.sensor)
+            && (self.quantities.is_empty() || self.quantities.iter().any(|q| q == reading.quantity.name()))
+    }
+
+    /// Never blocks, if the sink has fallen this far behind the item is dropped
+    fn send(&mut self, item: Item) {
+        let sender = match self.sender {
+            Some(ref sender) => sender,
+            None => return,
+        };
+        match sender.try_send(item) {
+            Ok(_) => {
+                if self.dropped > 0 {
+                    info!("Sink {} is keeping up again, {} deliveries were dropped", self.name, self.dropped);
+                    self.dropped = 0;
+                }
+            }
+            Err(TrySendError::Full(_)) => {
+                if self.dropped == 0 {
+                    warn!("Sink {} has fallen behind and its queue is full, dropping deliveries until it catches up", self.name);
+                }
+                self.dropped += 1;
+            }
+            Err(TrySendError::Disconnected(_)) => {
+                error!("Sink {} has stopped, nothing more will be sent to it", self.name);
+                self.sender = None;
+            }
+        }
+    }
+}
+
+/// Fans readings, events and health out to every configured sink
+pub struct Sinks {
+    handles: Vec<Handle>,
+}
+
+impl Sinks {
+    /// Creates every sink in the config and starts its thread, a sink which can't be created is logged and left out
+    pub fn start(config: &Config, shutdown: &Shutdown) -> Sinks {
+        let mut handles = Vec::new();
+        for sink_config in &config.sinks {
+            match create(sink_config, config, shutdown) {
+                Ok(sink) => {
+                    info!("Starting sink {}", sink_config.name);
+                    let (sender, receiver) = mpsc::sync_channel(sink_config.queue_size);
+                    let thread = {
+                        let (name, shutdown) = (sink_config.name.clone(), shutdown.clone());
+                        thread::spawn(move || run(sink, &name, receiver, &shutdown))
+                    };
+                    handles.push(Handle {
+                        name: sink_config.name.clone(),
+                        sensors: sink_config.sensors.clone(),
+                        quantities: sink_config.quantities.clone(),
+                        sender: Some(sender),
+                        thread: Some(thread),
+                        dropped: 0,
+                    });
+                }
+                Err(err) => {
+                    error!("Failed to create sink {}: {}, nothing will be sent to it", sink_config.name, err);
+                }
+            }
+        }
+        Sinks {
+            handles,
+        }
+    }
+
+    pub fn readings(&mut self, readings: &[Reading]) {
+        for handle in self.handles.iter_mut() {
+            let wanted: Vec<Reading> = readings.iter().filter(|reading| handle.wants(reading)).cloned().collect();
+            if !wanted.is_empty() {
+                handle.send(Item::Readings(wanted));
+            }
+        }
+    }
+
+    pub fn event(&mut self, event: &Event) {
+        for handle in self.handles.iter_mut() {
+            if handle.wants_sensor(event.sensor) {
+                handle.send(Item::Event(event.clone()));
+            }
+        }
+    }
+
+    pub fn health(&mut self, health: &Health) {
+        for handle in self.handles.iter_mut() {
+            if handle.wants_sensor(health.sensor) {
+                handle.send(Item::Health(health.clone()));
+            }
+        }
+    }
+
+    /// Lets every sink finish what is queued and flush, a sink which is failing gives up on what it has left
+    pub fn shutdown(self) {
+        let mut threads = Vec::new();
+        for mut handle in self.handles {
+            //Closing the queue is what tells the thread to finish
+            handle.sender.take();
+            if let Some(thread) = handle.thread.take() {
+                threads.push((handle.name, thread));
+            }
+        }
+        for (name, thread) in threads {
+            if thread.join().is_err() {
+                error!("The thread for sink {} panicked", name);
+            }
+        }
+    }
+}
+
+fn create(sink_config: &SinkConfig, config: &Config, shutdown: &Shutdown) -> Result<Box<dyn Sink>, Error> {
+    match sink_config.kind {
+        SinkKind::Api(ref api) => Ok(Box::new(api::ApiSink::new(api, config.location, shutdown.clone())?)),
+        SinkKind::Archive(ref archive) => Ok(Box::new(archive::ArchiveSink::new(archive.clone(), config.location)?)),
+        SinkKind::History(ref history) => Ok(Box::new(history::HistorySink::new(history.clone())?)),
+        SinkKind::Http(ref http) => Ok(Box::new(http::HttpSink::new(http.clone(), config.clone()))),
+        SinkKind::Influx(ref influx) => Ok(Box::new(influx::InfluxSink::new(influx.clone(), config.clone()))),
+        SinkKind::Loki(ref loki) => Ok(Box::new(loki::LokiSink::new(loki.clone(), config.location))),
+        SinkKind::Prometheus(ref prometheus) => Ok(Box::new(prometheus::PrometheusSink::new(prometheus, config.location, shutdown.clone())?)),
+    }
+}
+
+fn run(mut sink: Box<dyn Sink>, name: &str, receiver: Receiver<Item>, shutdown: &Shutdown) {
+    let mut backoff = INITIAL_BACKOFF;
+    //Something which failed to deliver, it's retried before anything newer so the sink still gets things in order
+    let mut pending: Option<Item> = None;
+    loop {
+        let item = match pending.take() {
+            Some(item) => Some(item),
+            None => {
+                match receiver.recv_timeout(FLUSH_INTERVAL) {
+                    Ok(item) => Some(item),
+                    Err(RecvTimeoutError::Timeout) => None,
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+        };
+
+        let result = match item {
+            Some(item) => {
+                match deliver(&mut *sink, &item) {
+                    Ok(_) => sink.flush(false),
+                    Err(err) => {
+                        pending = Some(item);
+                        Err(err)
+                    }
+                }
+            }
+            None => sink.flush(false),
+        };
+        match result {
+            Ok(_) => {
+                backoff = INITIAL_BACKOFF;
+            }
+            Err(err) => {
+                error!("Sink {} failed: {}, trying again in {} seconds", name, err, backoff.as_secs());
+                if shutdown.sleep(backoff) {
+                    //Stopping, don't hold the app up retrying something which isn't working
+                    if pending.take().is_some() {
+                        warn!("Sink {} is stopping, dropping what it failed to deliver", name);
+                    }
+                    break;
+                }
+                backoff = cmp::min(backoff * 2, MAX_BACKOFF);
+            }
+        }
+    }
+
+    //Anything left in the queue is still sent while the sink is working
+    for item in receiver.try_iter() {
+        if let Err(err) = deliver(&mut *sink, &item) {
+            error!("Sink {} failed while stopping: {}, dropping what is left", name, err);
+            break;
+        }
+    }
+    if let Err(err) = sink.flush(true) {
+        error!("Sink {} failed to flush while stopping: {}", name, err);
+    }
+    info!("Stopped sink {}", name);
+}
+
+fn deliver(sink: &mut dyn Sink, item: &Item) -> Result<(), Error> {
+    match *item {
+        Item::Readings(ref readings) => sink.readings(readings),
+        Item::Event(ref event) => sink.event(event),
+        Item::Health(ref health) => sink.health(health),
+    }
+}
@@ -0,0 +1,85 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+
+use tiny_http::Method;
+
+use config::PrometheusSinkConfig;
+use error::Error;
+use payload::Reading;
+use server::{self, Response};
+use shutdown::Shutdown;
+use sink::Sink;
+use sync::lock;
+
+/// The latest value of every reading, keyed by quantity then sensor so each metric's samples are together
+#[derive(Default)]
+struct Latest {
+    readings: BTreeMap<(&'static str, &'static str), Reading>,
+    //When each sensor last sent readings, in milliseconds since the unix epoch
+    updated: BTreeMap<&'static str, u64>,
+}
+
+/// Serves the latest readings as Prometheus gauges on `/metrics`. Nothing is sent anywhere, readings are kept
+/// for the next scrape.
+pub struct PrometheusSink {
+    latest: Arc<Mutex<Latest>>,
+}
+
+impl PrometheusSink {
+    pub fn new(config: &PrometheusSinkConfig, location: u16, shutdown: Shutdown) -> Result<PrometheusSink, Error> {
+        let latest = Arc::new(Mutex::new(Latest::default()));
+        let handler_latest = Arc::clone(&latest);
+        server::start("metrics", &config.listen, shutdown, move |method, path| {
+            if path != "/metrics" {
+                return Response::not_found();
+            }
+            if *method != Method::Get {
+                return Response::method_not_allowed();
+            }
+            let latest = lock(&handler_latest);
+            Response::new(200, "text/plain; version=0.0.4; charset=utf-8", render(&latest, location).into_bytes())
+        })?;
+        Ok(PrometheusSink {
+            latest,
+        })
+    }
+}
+
+impl Sink for PrometheusSink {
+    fn readings(&mut self, readings: &[Reading]) -> Result<(), Error> {
+        let mut latest = lock(&self.latest);
+        for reading in readings {
+            latest.readings.insert((reading.quantity.name(), reading.sensor), reading.clone());
+            latest.updated.insert(reading.sensor, reading.timestamp);
+        }
+        Ok(())
+    }
+}
+
+/// The text exposition format, a gauge for each quantity labelled with the location and sensor
+fn render(latest: &Latest, location: u16) -> String {
+    let mut out = String::new();
+    let mut last_metric = "";
+    for (&(quantity, sensor), reading) in &latest.readings {
+        if quantity != last_metric {
+            let unit = reading.quantity.unit();
+            let _ = writeln!(out, "# HELP indoor_sensors_{} Latest {} reading{}", quantity, quantity,
+                             if unit.is_empty() { String::new() } else { format!(" in {}", unit) });
+            let _ = writeln!(out, "# TYPE indoor_sensors_{} gauge", quantity);
+            last_metric = quantity;
+        }
+        let _ = writeln!(out, "indoor_sensors_{}{{location=\"{}\",sensor=\"{}\"}} {}", quantity, location, sensor, reading.value);
+    }
+
+    //Lets an alert spot a sensor which has stopped sending, its gauges above keep their last value forever
+    if !latest.updated.is_empty() {
+        let _ = writeln!(out, "# HELP indoor_sensors_last_update_timestamp_seconds When the sensor last sent readings");
+        let _ = writeln!(out, "# TYPE indoor_sensors_last_update_timestamp_seconds gauge");
+        for (sensor, timestamp) in &latest.updated {
+            let _ = writeln!(out, "indoor_sensors_last_update_timestamp_seconds{{location=\"{}\",sensor=\"{}\"}} {}.{:03}",
+                             location, sensor, timestamp / 1000, timestamp % 1000);
+        }
+    }
+    out
+}
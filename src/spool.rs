@@ -0,0 +1,252 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::PathBuf;
+
+use config::{SpoolConfig, Overflow};
+use error::Error;
+
+/// A message which could not be sent, waiting on disk to be replayed.
+pub struct Spooled {
+    pub topic: String,
+    pub payload: Vec<u8>,
+}
+
+/// Messages which couldn't be sent while the broker was unavailable, kept on disk so they survive
+/// a restart and replayed oldest first once it's back.
+///
+/// Every message is its own file named by a sequence number, the topic on the first line and the
+/// payload as is after it.
+pub struct Spool {
+    dir: PathBuf,
+    max_messages: usize,
+    overflow: Overflow,
+    queue: VecDeque<u64>,
+    next_seq: u64,
+}
+
+impl Spool {
+    /// Opens the spool directory, picking up anything left in it from before a restart
+    pub fn open(config: &SpoolConfig) -> Result<Spool, Error> {
+        let dir = PathBuf::from(&config.dir);
+        fs::create_dir_all(&dir)?;
+
+        let mut seqs = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().map_or(false, |ext| ext == "msg") {
+                match path.file_stem().and_then(|stem| stem.to_str()).and_then(|stem| stem.parse::<u64>().ok()) {
+                    Some(seq) => seqs.push(seq),
+                    None => warn!("Ignoring unexpected file in the spool: {}", path.display()),
+                }
+            }
+        }
+        seqs.sort();
+        if !seqs.is_empty() {
+            info!("Found {} spooled messages in {} to send", seqs.len(), dir.display());
+        }
+
+        let next_seq = seqs.last().map_or(0, |seq| seq + 1);
+        Ok(Spool {
+            dir,
+            max_messages: config.max_messages,
+            overflow: config.overflow,
+            queue: seqs.into_iter().collect(),
+            next_seq,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Adds a message to the end of the spool, if it's full the overflow policy decides what is lost
+    pub fn push(&mut self, topic: &str, payload: &[u8]) -> Result<(), Error> {
+        if self.queue.len() >= self.max_messages {
+            match self.overflow {
+                Overflow::DropOldest => {
+                    warn!("Spool is full with {} messages, dropping the oldest", self.queue.len());
+                    self.pop()?;
+                }
+                Overflow::DropNewest => {
+                    warn!("Spool is full with {} messages, dropping the message to {}", self.queue.len(), topic);
+                    return Ok(());
+                }
+            }
+        }
+
+        let seq = self.next_seq;
+        //Write then rename so a crash part way through never leaves half a message to be replayed
+        let tmp_path = self.dir.join(format!("{:020}.tmp", seq));
+        {
+            let mut file = File::create(&tmp_path)?;
+            file.write_all(topic.as_bytes())?;
+            file.write_all(b"\n")?;
+            file.write_all(payload)?;
+        }
+        fs::rename(&tmp_path, self.path(seq))?;
+
+        self.next_seq = seq + 1;
+        self.queue.push_back(seq);
+        Ok(())
+    }
+
+    /// The oldest message without removing it, `pop` it once it has been sent
+    pub fn peek(&mut self) -> Result<Option<Spooled>, Error> {
+        while let Some(&seq) = self.queue.front() {
+            let contents = match fs::read(self.path(seq)) {
+                Ok(contents) => contents,
+                Err(err) => {
+                    error!("Failed to read spooled message {}: {}, dropping it", self.path(seq).display(), err);
+                    self.queue.pop_front();
+                    continue;
+                }
+            };
+            match contents.iter().position(|&b| b == b'\n') {
+                Some(newline) => {
+                    return Ok(Some(Spooled {
+                        topic: String::from_utf8_lossy(&contents[..newline]).into_owned(),
+                        payload: contents[newline + 1..].to_vec(),
+                    }));
+                }
+                None => {
+                    error!("Spooled message {} is corrupt, dropping it", self.path(seq).display());
+                    self.pop()?;
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Removes the oldest message
+    pub fn pop(&mut self) -> Result<(), Error> {
+        if let Some(seq) = self.queue.pop_front() {
+            fs::remove_file(self.path(seq))?;
+        }
+        Ok(())
+    }
+
+    fn path(&self, seq: u64) -> PathBuf {
+        self.dir.join(format!("{:020}.msg", seq))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    /// A fresh spool directory for each test, removed again when dropped
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> TempDir {
+            let dir = env::temp_dir().join(format!("indoor_sensors-spool-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&dir);
+            TempDir(dir)
+        }
+
+        fn config(&self, max_messages: usize, overflow: Overflow) -> SpoolConfig {
+            SpoolConfig {
+                dir: self.0.to_string_lossy().into_owned(),
+                max_messages,
+                overflow,
+            }
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn drain(spool: &mut Spool) -> Vec<(String, Vec<u8>)> {
+        let mut messages = Vec::new();
+        while let Some(message) = spool.peek().unwrap() {
+            messages.push((message.topic, message.payload));
+            spool.pop().unwrap();
+        }
+        messages
+    }
+
+    fn message(topic: &str, payload: &str) -> (String, Vec<u8>) {
+        (String::from(topic), payload.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn replays_oldest_first() {
+        let dir = TempDir::new("order");
+        let mut spool = Spool::open(&dir.config(10, Overflow::DropOldest)).unwrap();
+        spool.push("a", b"1").unwrap();
+        spool.push("b", b"2\nwith a newline").unwrap();
+        spool.push("c", b"").unwrap();
+        assert_eq!(spool.len(), 3);
+        assert_eq!(drain(&mut spool), vec![message("a", "1"), message("b", "2\nwith a newline"), message("c", "")]);
+        assert!(spool.is_empty());
+    }
+
+    #[test]
+    fn survives_a_restart_in_order() {
+        let dir = TempDir::new("restart");
+        {
+            let mut spool = Spool::open(&dir.config(10, Overflow::DropOldest)).unwrap();
+            for i in 0..12 {
+                spool.push("topic", i.to_string().as_bytes()).ok();
+                if i == 1 {
+                    //Sent before the restart
+                    spool.pop().unwrap();
+                    spool.pop().unwrap();
+                }
+            }
+        }
+        //A half written message from a crash is ignored
+        fs::write(dir.0.join(format!("{:020}.tmp", 99)), b"topic\npartial").unwrap();
+
+        let mut spool = Spool::open(&dir.config(20, Overflow::DropOldest)).unwrap();
+        assert_eq!(spool.len(), 10);
+        spool.push("topic", b"after").unwrap();
+        let payloads: Vec<String> = drain(&mut spool).into_iter().map(|(_, payload)| String::from_utf8(payload).unwrap()).collect();
+        assert_eq!(payloads, vec!["2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "after"]);
+    }
+
+    #[test]
+    fn drop_oldest_keeps_the_newest() {
+        let dir = TempDir::new("oldest");
+        let mut spool = Spool::open(&dir.config(2, Overflow::DropOldest)).unwrap();
+        spool.push("a", b"1").unwrap();
+        spool.push("b", b"2").unwrap();
+        spool.push("c", b"3").unwrap();
+        assert_eq!(spool.len(), 2);
+        assert_eq!(drain(&mut spool), vec![message("b", "2"), message("c", "3")]);
+        assert_eq!(fs::read_dir(&dir.0).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn drop_newest_keeps_the_oldest() {
+        let dir = TempDir::new("newest");
+        let mut spool = Spool::open(&dir.config(2, Overflow::DropNewest)).unwrap();
+        spool.push("a", b"1").unwrap();
+        spool.push("b", b"2").unwrap();
+        spool.push("c", b"3").unwrap();
+        assert_eq!(spool.len(), 2);
+        assert_eq!(drain(&mut spool), vec![message("a", "1"), message("b", "2")]);
+    }
+
+    #[test]
+    fn skips_corrupt_and_missing_messages() {
+        let dir = TempDir::new("corrupt");
+        let mut spool = Spool::open(&dir.config(10, Overflow::DropOldest)).unwrap();
+        spool.push("a", b"1").unwrap();
+        spool.push("b", b"2").unwrap();
+        spool.push("c", b"3").unwrap();
+        fs::write(spool.path(0), b"no newline").unwrap();
+        fs::remove_file(spool.path(1)).unwrap();
+        assert_eq!(drain(&mut spool), vec![message("c", "3")]);
+    }
+}
@@ -0,0 +1,10 @@
+use std::sync::{Mutex, MutexGuard};
+
+/// Locks `mutex` even if a thread panicked while holding it. Everything we keep behind a lock is replaced
+/// whole, so a panic can't leave it half updated and there's nothing to gain by spreading the panic.
+pub fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    match mutex.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
@@ -1,77 +1,115 @@
-use std::sync::mpsc::Sender;
-use std::sync::{Arc, Mutex};
-
-use rppal::gpio::Gpio;
-use rppal::i2c::I2c;
-use rppal::gpio::{InputPin, Level, Trigger};
-
-use as3935::interface::i2c::I2cAddress;
-use as3935::{
-    Event, HeadOfStormDistance, InterfaceSelection, ListeningParameters, SensorPlacing,
-    SignalVerificationThreshold, AS3935,
-};
-
-use Payload;
-
-#[derive(Debug)]
-pub struct Error {
-    message: String,
-}
-
-pub struct As3935 {
-    sender: Sender<Payload>,
-    ls: AS3935,
-}
-
-impl As3935 {
-    pub fn new(sender: Sender<Payload>, lock: Arc<Mutex<i32>>) -> Result<As3935, Error> {
-        info!("Create and Init As3935");
-
-        let gpio = Gpio::new().unwrap();
-
-        let as3935 = AS3935::new(
-            InterfaceSelection::I2c(I2c::with_bus(1).unwrap(), I2cAddress::default()),
-            gpio.get(6).unwrap().into_input(),
-            lock,
-        )
-        .unwrap();
-
-        Ok(As3935 {
-            sender: sender,
-            ls: as3935,
-        })
-    }
-
-    pub fn start_thread(mut sensor: As3935) {
-        let events = sensor
-            .ls
-            .listen(
-                ListeningParameters::default()
-                    .with_sensor_placing(SensorPlacing::Indoor)
-                    .with_signal_verification_threshold(
-                        SignalVerificationThreshold::new(0).unwrap(),
-                    ),
-            )
-            .unwrap();
-
-        std::thread::spawn(move || {
-            for event in events {
-                info!(
-                    "{}",
-                    match event {
-                        Event::Lightning(lightning) => format!(
-                            "Lightning detected: {}.",
-                            match lightning {
-                                HeadOfStormDistance::Kilometers(km) => format!("{} km", km),
-                                HeadOfStormDistance::OutOfRange => String::from("out of range"),
-                                HeadOfStormDistance::Overhead => String::from("overhead"),
-                            }
-                        ),
-                        Event::Noise => String::from("Noise detected."),
-                        Event::Disturbance => String::from("Disturber detected."),
-                    }
-                )
-            }
-        });
-    }
-}
+use std::sync::mpsc::Receiver;
+use std::sync::Arc;
+use std::mem;
+use std::time::Duration;
+
+use rppal::gpio::Gpio;
+use rppal::i2c::I2c;
+
+use as3935::interface::i2c::I2cAddress;
+use as3935::{
+    Event, HeadOfStormDistance, InterfaceSelection, ListeningParameters, SensorPlacing,
+    SignalVerificationThreshold, AS3935,
+};
+
+use bus::BusManager;
+use config::As3935Config;
+use error::Error;
+use payload::{Reading, Event as PayloadEvent, EventKind, StormDistance};
+use threads::{SensorDriver, Description, Tick};
+
+pub struct As3935 {
+    buses: Arc<BusManager>,
+    config: As3935Config,
+    ls: Option<AS3935>,
+    events: Option<Receiver<Event>>,
+    pending: Vec<PayloadEvent>,
+}
+
+impl As3935 {
+    pub fn new(buses: Arc<BusManager>, config: As3935Config) -> As3935 {
+        As3935 {
+            buses,
+            config,
+            ls: None,
+            events: None,
+            pending: Vec::new(),
+        }
+    }
+}
+
+impl SensorDriver for As3935 {
+    fn describe(&self) -> Description {
+        //Events arrive from the lib's own listener, we only need to check for them regularly
+        Description {
+            name: "as3935",
+            sample_interval: Duration::from_millis(1000),
+            publish_interval: Duration::from_millis(1000),
+            warmup: Duration::from_secs(0),
+        }
+    }
+
+    fn init(&mut self) -> Result<(), Error> {
+        info!("Create and Init As3935");
+
+        let gpio = Gpio::new()?;
+
+        //The lib opens the bus through rppal and does its own locking, hand it the lock every other device on the bus uses
+        let bus = self.buses.bus(&format!("/dev/i2c-{}", self.config.i2c_bus))?;
+        let mut as3935 = AS3935::new(
+            InterfaceSelection::I2c(I2c::with_bus(self.config.i2c_bus)?, I2cAddress::default()),
+            gpio.get(self.config.irq_pin)?.into_input(),
+            bus.shared_lock(),
+        )
+        .map_err(|err| Error::Device(format!("AS3935 Error: {:?}", err)))?;
+
+        let events = as3935
+            .listen(
+                ListeningParameters::default()
+                    .with_sensor_placing(SensorPlacing::Indoor)
+                    .with_signal_verification_threshold(
+                        SignalVerificationThreshold::new(0).map_err(|err| Error::Device(format!("AS3935 Error: {:?}", err)))?,
+                    ),
+            )
+            .map_err(|err| Error::Device(format!("AS3935 Error: {:?}", err)))?;
+
+        self.ls = Some(as3935);
+        self.events = Some(events);
+        Ok(())
+    }
+
+    fn sample(&mut self, _tick: &Tick) -> Result<(), Error> {
+        let events = match self.events {
+            Some(ref events) => events,
+            None => {
+                return Err(Error::NotInitialized);
+            }
+        };
+
+        for event in events.try_iter() {
+            let kind = match event {
+                Event::Lightning(lightning) => EventKind::Lightning(
+                    match lightning {
+                        HeadOfStormDistance::Kilometers(km) => StormDistance::Kilometers(km),
+                        HeadOfStormDistance::OutOfRange => StormDistance::OutOfRange,
+                        HeadOfStormDistance::Overhead => StormDistance::Overhead,
+                    }
+                ),
+                Event::Noise => EventKind::Noise,
+                Event::Disturbance => EventKind::Disturbance,
+            };
+            info!("{}", kind);
+            self.pending.push(PayloadEvent::new("as3935", kind));
+        }
+        Ok(())
+    }
+
+    fn events(&mut self) -> Vec<PayloadEvent> {
+        mem::replace(&mut self.pending, Vec::new())
+    }
+
+    fn aggregate(&mut self, _tick: &Tick) -> Result<Vec<Reading>, Error> {
+        //Lightning strikes are sent as events, there is nothing to aggregate
+        Ok(Vec::new())
+    }
+}
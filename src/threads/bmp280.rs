@@ -1,163 +1,111 @@
-
-use std::fmt::{Display,Formatter};
-
-use std::sync::mpsc::Sender;
-use std::sync::{Arc,Mutex};
-use std::thread;
-use std::time::SystemTime;
+use std::sync::Arc;
 use std::time::Duration;
 
-use Payload;
-use sensor_lib::SensorValue;
+use bus::{BusManager, I2cProxy};
+use config::Bmp280Config;
+use command::Command;
+use error::Error;
+use payload::{Reading, Quantity};
+use threads::{SensorDriver, Description, Tick};
 
 use bme280::BME280;
 use bme280::Error as Bme280Error;
-use linux_hal::{I2cdev, Delay};
+use linux_hal::Delay;
 use linux_hal::i2cdev::linux::LinuxI2CError;
 
 
-
-#[derive(Debug)]
-pub struct Error {
-    message: String,
-}
-
-impl From<LinuxI2CError> for Error {
-    fn from(err: LinuxI2CError) -> Self {
-        Error {
-            message: format!("I2C Error: {}", err),
-        }
-    }
-}
+//This "secondary" address is really the primary there is a little bit of a screw-up somewhere with this lib
+const ADDRESS: u8 = 0x77;
 
 impl From<Bme280Error<LinuxI2CError>> for Error {
     fn from(err: Bme280Error<LinuxI2CError>) -> Self {
         match err {
-            Bme280Error::CompensationFailed => {
-                Error {
-                    message: String::from("Compensation Failed"),
-                }
-            },
-            Bme280Error::I2c(i2c_error) => {
-                Error {
-                    message: format!("BME Error: {}", i2c_error),
-                }
-            },
-            Bme280Error::InvalidData => {
-                Error {
-                    message: String::from("Invalid Data"),
-                }
-            },
-            Bme280Error::NoCalibrationData => {
-                Error {
-                    message: String::from("No Calibration Data"),
-                }
-            },
-            Bme280Error::UnsupportedChip => {
-                Error {
-                    message: String::from("Unsupported Chip"),
-                }
-            },
+            Bme280Error::CompensationFailed => Error::Device(String::from("Compensation Failed")),
+            Bme280Error::I2c(i2c_error) => Error::from(i2c_error),
+            Bme280Error::InvalidData => Error::Protocol(String::from("Invalid Data")),
+            Bme280Error::NoCalibrationData => Error::Device(String::from("No Calibration Data")),
+            Bme280Error::UnsupportedChip => Error::Device(String::from("Unsupported Chip")),
         }
-
-    }
-}
-
-impl Display for Error {
-    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
-        write!(f, "{}", self.message)
     }
 }
 
 pub struct Bmp280 {
-    sender: Sender<Payload>,
-    lock: Arc<Mutex<i32>>,
-    bme280: BME280<I2cdev, Delay>,
+    buses: Arc<BusManager>,
+    bme280: Option<BME280<I2cProxy, Delay>>,
+    config: Bmp280Config,
+    pressure: Option<f32>,
 }
 
 impl Bmp280 {
-    pub fn new(sender: Sender<Payload>, lock: Arc<Mutex<i32>>) -> Result<Bmp280, Error> {
+    pub fn new(buses: Arc<BusManager>, config: Bmp280Config) -> Bmp280 {
+        Bmp280 {
+            buses,
+            bme280: None,
+            config,
+            pressure: None,
+        }
+    }
+}
+
+impl SensorDriver for Bmp280 {
+    fn describe(&self) -> Description {
+        Description {
+            name: "bmp280",
+            //Read and send value every interval (5 mins by default)
+            sample_interval: Duration::from_secs(self.config.interval_secs),
+            publish_interval: Duration::from_secs(self.config.interval_secs),
+            warmup: Duration::from_secs(0),
+        }
+    }
+
+    fn init(&mut self) -> Result<(), Error> {
         info!("Create and Init BMP280");
-        let dev = I2cdev::new("/dev/i2c-1")?;
-        //This "secondary" address is really the primary there is a little bit of a screw-up somewhere with this lib
+        let dev = self.buses.proxy(&self.config.device, ADDRESS)?;
         let mut bme280 = BME280::new_secondary(dev, Delay);
         bme280.init()?;
-
-        Ok(Bmp280{
-            sender,
-            lock,
-            bme280: bme280,
-        })
+        self.bme280 = Some(bme280);
+        Ok(())
     }
 
-    pub fn start_thread(mut bmp280: Bmp280){
-        thread::spawn( move|| {
-            info!("Started BMP280 Thread");
-            loop {
-
-
-                //////////////////////////
-                // AIR PRESSURE
-                //////////////////////////
-
-                //Read and send value every 5 mins
-                thread::sleep(Duration::from_millis(299000));
-
-                let mut measurements = None;
+    fn sample(&mut self, _tick: &Tick) -> Result<(), Error> {
 
-                match bmp280.lock.lock(){
-                    Ok(_) => {
-                        match bmp280.bme280.measure(){
-                            Ok(result) => {
-                                measurements = Some(result);
-                            },
-                            Err(err) => {
-                                error!("Failed to read bmp280: {:?}", err);
-                            },
-                        }
-                    },
-                    Err(_) => {
-                        error!("The lock has been poisoned, sending a poison message to kill the app");
-                        bmp280.sender.send(Payload{
-                            queue: String::from("poison"),
-                            bytes: String::from("poison")
-                        }).unwrap(); //We don't really care anymore if this thread panics
-                    },
-                }
+        //////////////////////////
+        // AIR PRESSURE
+        //////////////////////////
 
-                match measurements {
-                    Some(measurement) => {
-                        let mut buf = [b'\0'; 30];
-                        //Convert to inches of mercury before sending
-                        let len = dtoa::write(&mut buf[..], measurement.pressure/3386.389).unwrap();
-                        let flt_as_string = std::str::from_utf8(&buf[..len]).unwrap();
+        match self.bme280 {
+            Some(ref mut bme280) => {
+                let measurement = bme280.measure()?;
+                self.pressure = Some(measurement.pressure);
+                Ok(())
+            }
+            None => {
+                Err(Error::NotInitialized)
+            }
+        }
+    }
 
-                        let temp_val = SensorValue {
-                            id: 54,
-                            timestamp: SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis() as u64,
-                            value: String::from(flt_as_string),
-                        };
+    fn aggregate(&mut self, tick: &Tick) -> Result<Vec<Reading>, Error> {
+        let mut readings = Vec::new();
+        if let Some(pressure) = self.pressure.take() {
+            readings.push(Reading {
+                sensor: "bmp280",
+                quantity: Quantity::Pressure,
+                //Convert to inches of mercury before sending
+                value: (pressure / 3386.389) as f64,
+                timestamp: tick.timestamp,
+            });
+        }
+        Ok(readings)
+    }
 
-                        match serde_json::to_string(&temp_val) {
-                            Ok(val) => {
-                                match bmp280.sender.send(Payload{
-                                    queue: String::from("/ws/2/grp/generic"),
-                                    bytes: val
-                                }){
-                                    Ok(_) => {},
-                                    Err(err) => {
-                                        error!("Failed to send message to main thread: {}", err);
-                                    },
-                                }
-                            }
-                            Err(err) => {
-                                error!("Failed to serialize the sensor value: {}", err);
-                            }
-                        };
-                    },
-                    None => {},
-                }
+    fn command(&mut self, command: &Command) -> Result<String, Error> {
+        match *command {
+            Command::SetInterval { interval_secs } => {
+                self.config.interval_secs = interval_secs;
+                Ok(format!("Readings will be sent every {} seconds", interval_secs))
             }
-        });
+            _ => Err(Error::Unsupported),
+        }
     }
-}
\ No newline at end of file
+}
@@ -1,163 +1,120 @@
-use std::fmt::{Display,Formatter};
-
-use std::sync::mpsc::Sender;
 use std::sync::{Arc,Mutex};
-use std::thread;
-use std::time::SystemTime;
 use std::time::Duration;
 
-use Payload;
-use sensor_lib::TempHumidityValue;
+use bus::{BusManager, I2cProxy};
+use config::Htu21dConfig;
+use command::Command;
+use error::Error;
+use payload::{Reading, Quantity};
+use sync::lock;
+use threads::{SensorDriver, Description, Tick};
 
-use linux_hal::{I2cdev, Delay};
+use linux_hal::Delay;
 use linux_hal::i2cdev::linux::LinuxI2CError;
 use htu21d::HTU21D;
 use htu21d::Error as Htu21dError;
 
-#[derive(Debug)]
-pub struct Error {
-    message: String,
-}
-
-impl From<LinuxI2CError> for Error {
-    fn from(err: LinuxI2CError) -> Self {
-        Error {
-            message: format!("I2C Error: {}", err),
-        }
-    }
-}
+const ADDRESS: u8 = 0x40;
 
 impl From<Htu21dError<LinuxI2CError>> for Error {
     fn from(err: Htu21dError<LinuxI2CError>) -> Self {
         match err {
-            Htu21dError::I2c(i2c_error) => {
-                Error {
-                    message: format!("HTU21 I2C Error: {}", i2c_error),
-                }
-            },
+            Htu21dError::I2c(i2c_error) => Error::from(i2c_error),
         }
     }
 }
 
-impl Display for Error {
-    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
-        write!(f, "{}", self.message)
-    }
-}
-
-
 pub struct Htu21d {
-    sender: Sender<Payload>,
-    lock: Arc<Mutex<i32>>,
-    htu21d: HTU21D<I2cdev, Delay>,
+    buses: Arc<BusManager>,
+    htu21d: Option<HTU21D<I2cProxy, Delay>>,
     humidity_mutex: Arc<Mutex<(f32,f32)>>,
+    config: Htu21dConfig,
+    latest: Option<(f32, f32)>,
 }
 
 impl Htu21d {
-    pub fn new(sender: Sender<Payload>, lock: Arc<Mutex<i32>>, humidity_mutex: Arc<Mutex<(f32,f32)>>) -> Result<Htu21d, Error> {
+    pub fn new(buses: Arc<BusManager>, humidity_mutex: Arc<Mutex<(f32,f32)>>, config: Htu21dConfig) -> Htu21d {
+        Htu21d {
+            buses,
+            htu21d: None,
+            humidity_mutex,
+            config,
+            latest: None,
+        }
+    }
+}
+
+impl SensorDriver for Htu21d {
+    fn describe(&self) -> Description {
+        Description {
+            name: "htu21d",
+            //Read and send value every interval, 1 min by default
+            sample_interval: Duration::from_secs(self.config.interval_secs),
+            publish_interval: Duration::from_secs(self.config.interval_secs),
+            warmup: Duration::from_secs(0),
+        }
+    }
+
+    fn init(&mut self) -> Result<(), Error> {
         info!("Create and Init HTU21Df");
-        let dev = I2cdev::new("/dev/i2c-1")?;
+        let dev = self.buses.proxy(&self.config.device, ADDRESS)?;
         let mut htu21d = HTU21D::new(dev, Delay);
         htu21d.reset()?;
-
-        Ok(Htu21d{
-            sender,
-            lock,
-            htu21d,
-            humidity_mutex
-        })
+        self.htu21d = Some(htu21d);
+        Ok(())
     }
 
-    pub fn start_thread(mut htu: Htu21d){
-        thread::spawn(move || {
-            info!("Started HTU21D Thread");
-            loop {
-                //Read and send value every 1 mins (add a few milliseconds to hopefully reduce collisions on mutex blocking)
-                thread::sleep(Duration::from_millis(60005));
-
-                //////////////////////////
-                // TEMP AND HUMIDITY
-                //////////////////////////
-
-                let mut temp = None;
-                let mut humidity = None;
-
-                match htu.lock.lock() {
-                    Ok(_) => {
-                        match htu.htu21d.read_temperature() {
-                            Ok(val) => {
-                                temp = Some(val);
-                            },
-                            Err(err) => {
-                                error!("Failed to read temp from HTU21D: {:?}", err);
-                            },
-                        }
-                        match htu.htu21d.read_humidity() {
-                            Ok(val) => {
-                                humidity = Some(val);
-                            },
-                            Err(err) => {
-                                error!("Failed to read humidity from HTU21D: {:?}", err);
-                            },
-                        }
-                    },
-                    Err(_) => {
-                        error!("The lock has been poisoned, sending a poison message to kill the app");
-                        htu.sender.send(Payload{
-                            queue: String::from("poison"),
-                            bytes: String::from("poison")
-                        }).unwrap(); //We don't really care anymore if this thread panics
-                    },
-                }
-
-                if let (Some(temp_val), Some(hum_val)) = (temp, humidity){
-
-                    //Update the humidity mutex for the sgp30 to use
-                    match htu.humidity_mutex.lock(){
-                        Ok(mut mut_val) => {
-                            *mut_val = (temp_val, hum_val);
-                        },
-                        Err(_) => {
-                            error!("The lock has been poisoned, sending a poison message to kill the app");
-                            htu.sender.send(Payload{
-                                queue: String::from("poison"),
-                                bytes: String::from("poison")
-                            }).unwrap(); //We don't really care anymore if this thread panics
-                        },
-                    }
-
-                    let temp_f = temp_val as f32 * 1.8 + 32.0;
-
-
-                    let temp_humidity = TempHumidityValue {
-                        timestamp: SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis() as u64,
-                        location: 2,
-                        temp: temp_f,
-                        humidity: hum_val,
-                    };
-
-                    match serde_json::to_string(&temp_humidity) {
-                        Ok(val) => {
-                            match htu.sender.send(Payload{
-                                queue: String::from("/ws/2/grp/temp_humidity"),
-                                bytes: val
-                            }){
-                                Ok(_) => {},
-                                Err(err) => {
-                                    error!("Failed to send message to main thread: {}", err);
-                                },
-                            }
-                        }
-                        Err(err) => {
-                            error!("Failed to serialize the temp_humidity value: {}", err);
-                        }
-                    };
-                }
-
+    fn sample(&mut self, _tick: &Tick) -> Result<(), Error> {
 
+        //////////////////////////
+        // TEMP AND HUMIDITY
+        //////////////////////////
 
+        let (temp_val, hum_val) = match self.htu21d {
+            Some(ref mut htu21d) => {
+                (htu21d.read_temperature()?, htu21d.read_humidity()?)
             }
-        });
+            None => {
+                return Err(Error::NotInitialized);
+            }
+        };
+
+        //Update the humidity mutex for the sgp30 to use
+        *lock(&self.humidity_mutex) = (temp_val, hum_val);
+
+        self.latest = Some((temp_val, hum_val));
+        Ok(())
     }
 
-}
\ No newline at end of file
+    fn aggregate(&mut self, tick: &Tick) -> Result<Vec<Reading>, Error> {
+        let mut readings = Vec::new();
+        if let Some((temp_val, hum_val)) = self.latest.take() {
+            let temp_f = temp_val as f32 * 1.8 + 32.0;
+            let timestamp = tick.timestamp;
+
+            readings.push(Reading {
+                sensor: "htu21d",
+                quantity: Quantity::Temperature,
+                value: temp_f as f64,
+                timestamp,
+            });
+            readings.push(Reading {
+                sensor: "htu21d",
+                quantity: Quantity::Humidity,
+                value: hum_val as f64,
+                timestamp,
+            });
+        }
+        Ok(readings)
+    }
+
+    fn command(&mut self, command: &Command) -> Result<String, Error> {
+        match *command {
+            Command::SetInterval { interval_secs } => {
+                self.config.interval_secs = interval_secs;
+                Ok(format!("Readings will be sent every {} seconds", interval_secs))
+            }
+            _ => Err(Error::Unsupported),
+        }
+    }
+}
@@ -1,7 +1,71 @@
+use std::time::Duration;
+
+use command::Command;
+use error::Error;
+use payload::{Reading, Event};
+
+pub mod runner;
+pub mod schedule;
+pub mod supervisor;
 pub mod bmp280;
 pub mod sgp30;
 pub mod htu21d;
 pub mod geiger;
 pub mod sds011;
 pub mod radiothermostat;
-pub mod as3935;
\ No newline at end of file
+pub mod as3935;
+
+/// How a driver wants the runner to schedule it.
+pub struct Description {
+    pub name: &'static str,
+    /// Time between calls to `sample`, ticks are aligned to whole multiples of this on the wall clock
+    pub sample_interval: Duration,
+    /// How often `aggregate` is called, always on a sample tick
+    pub publish_interval: Duration,
+    /// How long the device needs between being asked for a measurement and having one, a `measure` command for a
+    /// sensor with a warmup brings its next publish tick forward instead of sampling straight away
+    pub warmup: Duration,
+}
+
+/// One firing of the schedule, passed to `sample` and `aggregate`.
+pub struct Tick {
+    /// Wall clock time the tick was scheduled for in milliseconds since the unix epoch, readings are stamped
+    /// with this so readings from different sensors line up
+    pub timestamp: u64,
+    /// `aggregate` will be called straight after `sample` on this tick
+    pub publish: bool,
+    /// Time left until the next publish tick, zero on a publish tick
+    pub until_publish: Duration,
+}
+
+/// Everything device specific about a sensor, the timing loop and sending of payloads is handled
+/// by the runner, drivers on a shared I2C bus go through the `BusManager` for their locking. Serializing readings is left to the output stage in main.
+pub trait SensorDriver: Send {
+    fn describe(&self) -> Description;
+
+    /// Open and configure the device, this is the first time a driver touches its bus or port.
+    fn init(&mut self) -> Result<(), Error>;
+
+    /// Called every `sample_interval`, drivers accumulate whatever they read until `aggregate` is called.
+    fn sample(&mut self, tick: &Tick) -> Result<(), Error>;
+
+    /// Called every `publish_interval` to turn the accumulated samples into readings to send,
+    /// they should all be stamped with the tick's timestamp.
+    fn aggregate(&mut self, tick: &Tick) -> Result<Vec<Reading>, Error>;
+
+    /// Called once when the app is stopping, leave the device in a safe state and save anything worth keeping.
+    fn shutdown(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Called after every `sample` to collect anything the driver noticed which isn't a reading.
+    fn events(&mut self) -> Vec<Event> {
+        Vec::new()
+    }
+
+    /// Called for a command sent over the command topic, returns what was done for the reply. `Measure` is
+    /// handled by the runner, a driver which changes its intervals for `SetInterval` is rescheduled afterwards.
+    fn command(&mut self, _command: &Command) -> Result<String, Error> {
+        Err(Error::Unsupported)
+    }
+}
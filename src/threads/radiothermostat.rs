@@ -1,84 +1,98 @@
 
-use std::sync::mpsc::Sender;
-use Payload;
+use config::ThermostatConfig;
+use command::Command;
+use error::Error;
+use payload::{Reading, Quantity};
+use threads::{SensorDriver, Description, Tick};
+
+use std::mem;
 
-use std::thread;
 use std::time::Duration;
-use std::time::SystemTime;
 
 use mio_httpc::CallBuilder;
 use mio_httpc::Error as MioError;
 
 use sensor_lib::ThermostatValue;
-use std::fmt::{Display,Formatter};
 
-#[derive(Debug)]
-pub struct Error {
-    message: String,
+/// The numeric fields of the thermostat's /tstat response and the quantity each is sent as
+pub const FIELDS: [(&str, Quantity); 9] = [
+    ("temp", Quantity::ThermostatTemperature),
+    ("t_heat", Quantity::ThermostatHeatSetpoint),
+    ("t_cool", Quantity::ThermostatCoolSetpoint),
+    ("tmode", Quantity::ThermostatMode),
+    ("fmode", Quantity::ThermostatFanMode),
+    ("tstate", Quantity::ThermostatState),
+    ("fstate", Quantity::ThermostatFanState),
+    ("hold", Quantity::ThermostatHold),
+    ("override", Quantity::ThermostatOverride),
+];
+
+pub struct RadioThermostat {
+    config: ThermostatConfig,
+    latest: Vec<Reading>,
 }
 
-impl Display for Error {
-    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
-        write!(f, "{}", self.message)
+impl RadioThermostat {
+    pub fn new(config: ThermostatConfig) -> RadioThermostat {
+        RadioThermostat {
+            config,
+            latest: Vec::new(),
+        }
     }
 }
 
-pub struct RadioThermostat {
-    sender: Sender<Payload>,
-}
+impl SensorDriver for RadioThermostat {
+    fn describe(&self) -> Description {
+        Description {
+            name: "thermostat",
+            sample_interval: Duration::from_secs(self.config.interval_secs),
+            publish_interval: Duration::from_secs(self.config.interval_secs),
+            warmup: Duration::from_secs(0),
+        }
+    }
 
-impl RadioThermostat {
-    pub fn new(sender: Sender<Payload>) -> Result<RadioThermostat, Error> {
-        Ok(RadioThermostat{
-            sender,
-        })
+    fn init(&mut self) -> Result<(), Error> {
+        //Nothing to open, the thermostat is queried fresh every sample
+        Ok(())
     }
 
-    pub fn start_thread(mut thermostat: RadioThermostat) {
-        thread::spawn(move || {
-            info!("Started Thermostat Thread");
-            loop {
-                thread::sleep(Duration::from_secs(60));
-                match query_thermostat() {
-                    Ok(response) => {
-                        match serde_json::from_slice::<ThermostatValue>(response.as_slice()){
-                            Ok(mut val) => {
-                                info!("Thermostat Val: {:?}", val);
-                                val.timestamp = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis() as u64;
-                                match serde_json::to_string(&val) {
-                                    Ok(serial_val) => {
-                                        match thermostat.sender.send(Payload{
-                                            queue: String::from("/ws/2/grp/thermostat"),
-                                            bytes: serial_val
-                                        }){
-                                            Ok(_) => {},
-                                            Err(err) => {
-                                                error!("Failed to send message to main thread: {}", err);
-                                            },
-                                        }
-                                    }
-                                    Err(err) => {
-                                        error!("Failed to serialize the thermostat value: {}", err);
-                                    }
-                                };
-                            },
-                            Err(err) => {
-                                error!("Failed to deserialize thermostat value: {}", err);
-                            },
-                        }
-                    },
-                    Err(err) => {
-                        error!("Error querying thermostat: {}", err);
-                    },
-                }
-            }
+    fn sample(&mut self, tick: &Tick) -> Result<(), Error> {
+        let response = query_thermostat(&self.config)?;
+        let val = serde_json::from_slice::<ThermostatValue>(response.as_slice())?;
+        info!("Thermostat Val: {:?}", val);
 
+        //Not every field is in every response (t_heat and t_cool depend on the mode), only send what we got
+        let fields = serde_json::to_value(&val)?;
+        let timestamp = tick.timestamp;
+        self.latest = FIELDS.iter()
+            .filter_map(|&(key, quantity)| {
+                fields.get(key).and_then(|field| field.as_f64()).map(|value| Reading {
+                    sensor: "thermostat",
+                    quantity,
+                    value,
+                    timestamp,
+                })
+            })
+            .collect();
+        Ok(())
+    }
 
-        });
+    fn aggregate(&mut self, _tick: &Tick) -> Result<Vec<Reading>, Error> {
+        Ok(mem::replace(&mut self.latest, Vec::new()))
+    }
+
+    fn command(&mut self, command: &Command) -> Result<String, Error> {
+        match *command {
+            Command::SetInterval { interval_secs } => {
+                self.config.interval_secs = interval_secs;
+                Ok(format!("Readings will be sent every {} seconds", interval_secs))
+            }
+            _ => Err(Error::Unsupported),
+        }
     }
 }
 
-fn query_thermostat() -> Result<Vec<u8>, MioError> {
-    let (response_meta, body) = CallBuilder::get().timeout_ms(20000).url("http://172.20.30.30/tstat")?.exec()?;
+fn query_thermostat(config: &ThermostatConfig) -> Result<Vec<u8>, MioError> {
+    let (_response_meta, body) = CallBuilder::get().timeout_ms(config.timeout_ms).url(&config.url)?.exec()?;
     Ok(body)
-}
\ No newline at end of file
+}
@@ -0,0 +1,188 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant, SystemTime};
+
+use command::{Command, Request};
+use payload::{Payload, Health, Status};
+use shutdown::Shutdown;
+use threads::SensorDriver;
+use threads::schedule::Schedule;
+use threads::supervisor::Heartbeat;
+
+/// A driver which keeps failing is initialized again every `reprobe_interval`, that's how a device which was
+/// unplugged and plugged back in is picked up again. Commands for the sensor arrive on `commands` and are
+/// handled between ticks.
+pub fn start_thread<D: SensorDriver + 'static>(driver: D, sender: Sender<Payload>, commands: Receiver<Request>, shutdown: Shutdown, heartbeat: Heartbeat, reprobe_interval: Duration) -> JoinHandle<()> {
+    let name = driver.describe().name;
+    thread::spawn(move || {
+        //A panic in a driver only ends this thread, the supervisor sees it stop and starts a new one
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            run(driver, &sender, &commands, &shutdown, &heartbeat, reprobe_interval);
+        }));
+        if result.is_err() {
+            error!("The {} thread panicked", name);
+        }
+        heartbeat.stop();
+    })
+}
+
+fn run<D: SensorDriver>(mut driver: D, sender: &Sender<Payload>, commands: &Receiver<Request>, shutdown: &Shutdown, heartbeat: &Heartbeat, reprobe_interval: Duration) {
+    let mut description = driver.describe();
+    let mut schedule = Schedule::new(&description);
+    heartbeat.expect_every(description.sample_interval);
+
+    info!("Started {} Thread", description.name);
+    //The driver was initialized successfully before the thread was started
+    let mut failing_since: Option<Instant> = None;
+    let mut last_init = Instant::now();
+    'ticks: loop {
+        heartbeat.beat();
+        let (tick, deadline) = schedule.next_tick();
+        //A command wakes us up early, once it's handled carry on waiting for the same tick. The supervisor drops
+        //its end of the channel when we are being shut down or replaced so that wakes us up too.
+        loop {
+            let now = Instant::now();
+            if now >= deadline {
+                break;
+            }
+            match commands.recv_timeout(deadline - now) {
+                Ok(request) => {
+                    let reschedule = handle_command(&mut driver, &mut schedule, request, sender);
+                    for event in driver.events() {
+                        send(sender, Payload::Event(event));
+                    }
+                    if reschedule {
+                        description = driver.describe();
+                        schedule = Schedule::new(&description);
+                        heartbeat.expect_every(description.sample_interval);
+                        continue 'ticks;
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => {
+                    shutdown.sleep(deadline - now);
+                    break;
+                }
+            }
+        }
+        if shutdown.is_triggered() {
+            break;
+        }
+        if heartbeat.is_abandoned() {
+            //The supervisor gave up on us and started another thread for this sensor, leave the device to it
+            warn!("{} Thread was replaced after stalling, exiting", description.name);
+            return;
+        }
+
+        match driver.sample(&tick) {
+            Ok(_) => {
+                if failing_since.take().is_some() {
+                    send_health(sender, description.name, Status::Up);
+                }
+            }
+            Err(err) => {
+                error!("Failed to sample {}: {}", description.name, err);
+                send_health(sender, description.name, Status::Error(err.to_string()));
+                if failing_since.is_none() {
+                    failing_since = Some(Instant::now());
+                    send_health(sender, description.name, Status::Failed(err.to_string()));
+                }
+                //Errors which aren't the device's fault can go away on their own, only start over once they've lasted a while
+                let failing_too_long = failing_since.map_or(false, |since| since.elapsed() >= reprobe_interval)
+                    && last_init.elapsed() >= reprobe_interval;
+                if err.needs_reinit() || failing_too_long {
+                    warn!("Initializing {} again to recover from: {}", description.name, err);
+                    last_init = Instant::now();
+                    if let Err(err) = driver.init() {
+                        error!("Failed to initialize {} again: {}", description.name, err);
+                    }
+                }
+            }
+        }
+
+        for event in driver.events() {
+            send(sender, Payload::Event(event));
+        }
+
+        if !tick.publish {
+            continue;
+        }
+
+        match driver.aggregate(&tick) {
+            Ok(readings) => {
+                if !readings.is_empty() {
+                    send(sender, Payload::Readings(readings));
+                }
+            }
+            Err(err) => {
+                error!("Failed to aggregate {}: {}", description.name, err);
+            }
+        }
+    }
+
+    info!("Stopping {} Thread", description.name);
+    if let Err(err) = driver.shutdown() {
+        error!("Failed to shut down {} cleanly: {}", description.name, err);
+    }
+}
+
+/// Carries out a command and sends the reply, returns true if the driver has to be scheduled again
+fn handle_command<D: SensorDriver>(driver: &mut D, schedule: &mut Schedule, request: Request, sender: &Sender<Payload>) -> bool {
+    let description = driver.describe();
+    info!("Received {} command for {}", request.command.name(), description.name);
+    let mut reschedule = false;
+    let result = match request.command {
+        Command::Measure if description.warmup > Duration::from_millis(0) => {
+            let until = schedule.publish_after(description.warmup);
+            Ok(format!("Measuring, readings will be sent in {} seconds", until.as_secs()))
+        }
+        Command::Measure => {
+            let tick = schedule.immediate_tick();
+            driver.sample(&tick)
+                .and_then(|_| driver.aggregate(&tick))
+                .map(|readings| {
+                    let message = format!("Sent {} readings", readings.len());
+                    if !readings.is_empty() {
+                        send(sender, Payload::Readings(readings));
+                    }
+                    message
+                })
+        }
+        Command::SetInterval { .. } => {
+            reschedule = true;
+            driver.command(&request.command)
+        }
+        _ => driver.command(&request.command),
+    };
+    match result {
+        Ok(message) => {
+            request.reply(Ok(message)).send(sender);
+        }
+        Err(err) => {
+            warn!("{} command for {} failed: {}", request.command.name(), description.name, err);
+            reschedule = false;
+            request.reply(Err(err.to_string())).send(sender);
+        }
+    }
+    reschedule
+}
+
+/// Shared with the supervisor, which reports on the threads from outside
+pub(crate) fn send_health(sender: &Sender<Payload>, sensor: &'static str, status: Status) {
+    send(sender, Payload::Health(Health {
+        sensor,
+        status,
+        timestamp: SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis() as u64,
+    }));
+}
+
+fn send(sender: &Sender<Payload>, payload: Payload) {
+    match sender.send(payload) {
+        Ok(_) => {}
+        Err(err) => {
+            error!("Failed to send message to main thread: {}", err);
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use std::time::{Duration, Instant, SystemTime};
+
+use threads::{Description, Tick};
+
+//If the wall clock and our monotonic timeline disagree by more than this the clock was stepped (NTP sync, someone
+//setting the date) and the ticks are lined up with it again
+const MAX_CLOCK_SKEW_MS: u64 = 1000;
+
+/// Works out when a runner's next sample and publish ticks are. Ticks fall on whole multiples of the interval
+/// since the unix epoch, so a 60 second interval ticks at the top of every minute and every sensor sharing an
+/// interval stamps its readings with the same time. Deadlines are tracked on the monotonic clock so processing
+/// time never adds up into drift.
+pub struct Schedule {
+    sample_ms: u64,
+    publish_ms: u64,
+    //A wall clock time and the instant it was taken at, every deadline is measured from these
+    anchor_wall: u64,
+    anchor: Instant,
+    last_tick: u64,
+    next_publish: u64,
+}
+
+impl Schedule {
+    pub fn new(description: &Description) -> Schedule {
+        let sample_ms = description.sample_interval.as_millis() as u64;
+        let publish_ms = description.publish_interval.as_millis() as u64;
+        let anchor_wall = wall_millis();
+        Schedule {
+            sample_ms: if sample_ms == 0 { 1 } else { sample_ms },
+            publish_ms: if publish_ms == 0 { 1 } else { publish_ms },
+            anchor_wall,
+            anchor: Instant::now(),
+            last_tick: 0,
+            next_publish: 0,
+        }
+    }
+
+    /// The next tick after now, a tick which was missed because the last one overran is skipped rather than run late
+    pub fn next_tick(&mut self) -> (Tick, Instant) {
+        let now = self.now();
+        let tick = self.tick_after(now);
+        let deadline = self.anchor + Duration::from_millis(tick.timestamp - self.anchor_wall);
+        (tick, deadline)
+    }
+
+    fn tick_after(&mut self, now: u64) -> Tick {
+        let mut at = (now / self.sample_ms + 1) * self.sample_ms;
+        if at <= self.last_tick {
+            at = self.last_tick + self.sample_ms;
+        }
+        self.last_tick = at;
+
+        if self.next_publish == 0 {
+            self.next_publish = next_boundary(at, self.publish_ms);
+        }
+        let publish = at >= self.next_publish;
+        if publish {
+            self.next_publish = next_boundary(at + 1, self.publish_ms);
+        }
+
+        Tick {
+            timestamp: at,
+            publish,
+            until_publish: if publish { Duration::from_millis(0) } else { Duration::from_millis(self.next_publish - at) },
+        }
+    }
+
+    /// A publish tick for right now, off the grid, for a measurement which was asked for
+    pub fn immediate_tick(&mut self) -> Tick {
+        Tick {
+            timestamp: self.now(),
+            publish: true,
+            until_publish: Duration::from_millis(0),
+        }
+    }
+
+    /// Brings the next publish tick forward to the first sample tick at least `delay` from now, the ticks after it
+    /// go back to the usual boundaries
+    pub fn publish_after(&mut self, delay: Duration) -> Duration {
+        let now = self.now();
+        let at = next_boundary(now + delay.as_millis() as u64, self.sample_ms);
+        if self.next_publish == 0 || at < self.next_publish {
+            self.next_publish = at;
+        }
+        Duration::from_millis(self.next_publish.saturating_sub(now))
+    }
+
+    /// Milliseconds since the unix epoch on our monotonic timeline
+    fn now(&mut self) -> u64 {
+        let elapsed = self.anchor.elapsed().as_millis() as u64;
+        self.reconcile(elapsed, wall_millis())
+    }
+
+    /// Our time from `elapsed` milliseconds since the anchor, unless the wall clock says otherwise by too much
+    fn reconcile(&mut self, elapsed: u64, wall: u64) -> u64 {
+        let monotonic = self.anchor_wall + elapsed;
+        let skew = if wall > monotonic { wall - monotonic } else { monotonic - wall };
+        if skew <= MAX_CLOCK_SKEW_MS {
+            return monotonic;
+        }
+        info!("Wall clock moved by {}ms, lining ticks up with it again", skew);
+        self.anchor_wall = wall;
+        self.anchor = Instant::now();
+        self.last_tick = 0;
+        self.next_publish = 0;
+        wall
+    }
+}
+
+/// The first multiple of `interval` at or after `at`
+fn next_boundary(at: u64, interval: u64) -> u64 {
+    ((at + interval - 1) / interval) * interval
+}
+
+fn wall_millis() -> u64 {
+    SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule(sample_secs: u64, publish_secs: u64) -> Schedule {
+        let mut schedule = Schedule::new(&Description {
+            name: "test",
+            sample_interval: Duration::from_secs(sample_secs),
+            publish_interval: Duration::from_secs(publish_secs),
+            warmup: Duration::from_secs(0),
+        });
+        schedule.anchor_wall = 1_000_000;
+        schedule
+    }
+
+    #[test]
+    fn ticks_fall_on_interval_boundaries() {
+        let mut schedule = schedule(1, 60);
+        let tick = schedule.tick_after(1_010_500);
+        assert_eq!(tick.timestamp, 1_011_000);
+        assert!(!tick.publish);
+        assert_eq!(tick.until_publish, Duration::from_millis(9_000));
+    }
+
+    #[test]
+    fn publishes_on_the_publish_boundary_only() {
+        let mut schedule = schedule(1, 60);
+        let tick = schedule.tick_after(1_019_999);
+        assert_eq!(tick.timestamp, 1_020_000);
+        assert!(tick.publish);
+        assert_eq!(tick.until_publish, Duration::from_millis(0));
+
+        let tick = schedule.tick_after(1_020_001);
+        assert_eq!(tick.timestamp, 1_021_000);
+        assert!(!tick.publish);
+        assert_eq!(tick.until_publish, Duration::from_millis(59_000));
+    }
+
+    #[test]
+    fn missed_ticks_are_skipped_not_repeated() {
+        let mut schedule = schedule(1, 60);
+        assert_eq!(schedule.tick_after(1_010_500).timestamp, 1_011_000);
+        //The sample overran by a couple of ticks
+        assert_eq!(schedule.tick_after(1_013_500).timestamp, 1_014_000);
+        //Woken a little early, the same tick never fires twice
+        assert_eq!(schedule.tick_after(1_013_900).timestamp, 1_015_000);
+    }
+
+    #[test]
+    fn a_missed_publish_tick_publishes_on_the_next_tick() {
+        let mut schedule = schedule(1, 60);
+        assert!(!schedule.tick_after(1_018_500).publish);
+        let tick = schedule.tick_after(1_021_500);
+        assert_eq!(tick.timestamp, 1_022_000);
+        assert!(tick.publish);
+        assert!(!schedule.tick_after(1_022_500).publish);
+    }
+
+    #[test]
+    fn small_skew_keeps_the_monotonic_time() {
+        let mut schedule = schedule(1, 60);
+        assert_eq!(schedule.reconcile(5_000, 1_005_400), 1_005_000);
+        assert_eq!(schedule.reconcile(5_000, 1_004_100), 1_005_000);
+        assert_eq!(schedule.anchor_wall, 1_000_000);
+    }
+
+    #[test]
+    fn a_clock_step_lines_the_ticks_up_again() {
+        let mut schedule = schedule(1, 60);
+        schedule.tick_after(1_005_500);
+        assert_ne!(schedule.next_publish, 0);
+
+        //NTP stepped the clock forward an hour
+        let now = schedule.reconcile(6_000, 4_606_000);
+        assert_eq!(now, 4_606_000);
+        assert_eq!(schedule.anchor_wall, 4_606_000);
+        assert_eq!(schedule.last_tick, 0);
+        assert_eq!(schedule.next_publish, 0);
+
+        let tick = schedule.tick_after(now);
+        assert_eq!(tick.timestamp, 4_607_000);
+        assert_eq!(tick.until_publish, Duration::from_millis(13_000));
+    }
+
+    #[test]
+    fn next_boundary_rounds_up() {
+        assert_eq!(next_boundary(60_000, 60_000), 60_000);
+        assert_eq!(next_boundary(60_001, 60_000), 120_000);
+        assert_eq!(next_boundary(0, 1_000), 0);
+    }
+}
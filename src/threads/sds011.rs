@@ -1,59 +1,56 @@
-use std::fmt::{Display, Formatter};
-
-use std::sync::mpsc::Sender;
 use std::thread;
-use std::time::SystemTime;
 use std::time::Duration;
 use std::path::Path;
 use std::io::prelude::*;
-use std::io::Error as IoError;
 use std::collections::VecDeque;
+use std::mem;
 
-use Payload;
-use sensor_lib::AirParticulateValue;
+use command::Command;
+use config::Sds011Config;
+use error::{Error, ConfigError};
+use payload::{Reading, Quantity, Event, EventKind};
+use threads::{SensorDriver, Description, Tick};
 
 use serial::prelude::*;
 use serial::unix::TTYPort;
-use serial::core::Error as SerialError;
 
 
-#[derive(Debug)]
-pub struct Error {
-    message: String,
+pub struct Sds011 {
+    port: Option<TTYPort>,
+    config: Sds011Config,
+    powered: bool,
+    pm2_5_queue: VecDeque<i32>,
+    pm10_queue: VecDeque<i32>,
+    events: Vec<Event>,
 }
 
-impl From<SerialError> for Error {
-    fn from(err: SerialError) -> Self {
-        Error {
-            message: format!("{}", err),
+impl Sds011 {
+    pub fn new(config: Sds011Config) -> Sds011 {
+        Sds011 {
+            port: None,
+            config,
+            powered: false,
+            pm2_5_queue: VecDeque::<i32>::with_capacity(30),
+            pm10_queue: VecDeque::<i32>::with_capacity(30),
+            events: Vec::new(),
         }
     }
 }
 
-impl From<IoError> for Error {
-    fn from(err: IoError) -> Self {
-        Error{
-            message: format!("{}", err),
+impl SensorDriver for Sds011 {
+    fn describe(&self) -> Description {
+        Description {
+            name: "sds011",
+            sample_interval: Duration::from_millis(1000),
+            publish_interval: Duration::from_secs(self.config.interval_secs),
+            //A minute for the fan to run and the readings to settle, then 30 seconds of samples
+            warmup: Duration::from_secs(60),
         }
     }
-}
-
-
-impl Display for Error {
-    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
-        write!(f, "{}", self.message)
-    }
-}
 
-pub struct Sds011 {
-    sender: Sender<Payload>,
-    port: TTYPort,
-}
-
-impl Sds011 {
-    pub fn new(sender: Sender<Payload>) -> Result<Sds011, Error> {
+    fn init(&mut self) -> Result<(), Error> {
         info!("Setup air quality monitor serial port");
-        let mut air_port = serial::unix::TTYPort::open(Path::new("/dev/serial0"))?;
+        let mut air_port = serial::unix::TTYPort::open(Path::new(&self.config.device))?;
         let settings = serial::PortSettings {
             baud_rate: serial::Baud9600,
             char_size: serial::Bits8,
@@ -65,234 +62,211 @@ impl Sds011 {
         air_port.set_timeout(Duration::from_millis(250))?;
 
         //for some reason we get a bogus response the first time we send a command after startup, so we retry this guy a few times
+        let mut result = Err(Error::Timeout);
         for _x in 0..3 {
             info!("Changing air monitor to query only mode");
             let cmd = [0xAAu8, 0xB4, 0x02, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0x02, 0xAB];
-            air_port.write(&cmd)?;
+            air_port.write_all(&cmd)?;
 
             //These commands seem to take some time to process, so we just wait a little bit before checking for a response
             thread::sleep(Duration::from_millis(500));
 
-            //Read the response
-            let mut buf: Vec<u8> = (0..20).collect();
-            match air_port.read(&mut buf[..]) {
-                Ok(t) => {
-                    if t == 10 {
-                        if buf.get(1) == Some(&0xC5) && buf.get(2) == Some(&0x02) && buf.get(3) == Some(&0x01) && buf.get(4) == Some(&0x01) {
-                            info!("Air Particulate Sensor Successfully Changed to query only mode");
-                            break;
-                        } else {
-                            error!("Failed to put air sensor into query only mode, Received: {:?}", buf);
-                        }
-                    } else {
-                        error!("Failed to put air sensor into query only mode: Packet from air sensor was incorrect length: {:?}", buf);
-                    }
+            result = read_reply(&mut air_port).and_then(|buf| {
+                if buf[1] == 0xC5 && buf[2] == 0x02 && buf[3] == 0x01 && buf[4] == 0x01 {
+                    Ok(())
+                } else {
+                    Err(Error::Protocol(format!("Unexpected reply to query only mode: {:?}", buf)))
+                }
+            });
+            match result {
+                Ok(_) => {
+                    info!("Air Particulate Sensor Successfully Changed to query only mode");
+                    break;
                 }
-                Err(e) => {
-                    error!("Failed to read from air sensor serial port: {}", e);
+                Err(ref err) => {
+                    error!("Failed to put air sensor into query only mode: {}", err);
                 }
             }
         }
+        //Nothing answering at all means there's no SDS011 on this port
+        result?;
 
+        turn_off(&mut air_port)?;
 
-        info!("Turning off air monitor");
-        let cmd = [0xAAu8, 0xB4, 0x06, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0x05, 0xAB];
-        air_port.write(&cmd)?;
-
-        //These commands seem to take some time to process, so we just wait a little bit before checking for a response
-        thread::sleep(Duration::from_millis(500));
+        self.port = Some(air_port);
+        self.powered = false;
+        Ok(())
+    }
 
-        let mut buf: Vec<u8> = (0..20).collect();
-        match air_port.read(&mut buf[..]) {
-            Ok(t) => {
-                if t == 10 {
-                    if buf.get(1) == Some(&0xC5) && buf.get(2) == Some(&0x06) && buf.get(3) == Some(&0x01) && buf.get(4) == Some(&0x00) {
-                        info!("Air Particulate Sensor Successfully Turned Off")
-                    } else {
-                        error!("Failed to turn off air sensor!, Received: {:?}", buf);
-                    }
-                } else {
-                    error!("Failed to turn off air sensor: Packet from air sensor was incorrect length: {:?}", buf);
-                }
-            }
-            Err(e) => {
-                error!("Failed to read from air sensor serial port: {}", e);
-            }
-        }
+    fn sample(&mut self, tick: &Tick) -> Result<(), Error> {
 
-        Ok(Sds011 {
-            sender,
-            port: air_port,
-        })
-    }
+        // The datasheet says the sensor has a lifespan of 8000 hours, which if we left it on all the time would not last us very long, about a year.
+        // So to extend the life we are going to basically go with a dutycycle of being on for one minute out of every five minutes
+        // This should result in 24hrs * 60mins = 1440 mins/day
+        // 1440 / 5 = 288 five min intervals, where we are on for one minute.
+        // Which should add up to being on for 288 mins a day
+        // 8000 hrs = 480000 mins, 480000/288 = 1666 days or about 4.5 years
 
-    pub fn start_thread(mut sds011: Sds011) {
-        info!("Started SDS011 Thread");
-        let mut counter = 0;
-        let mut pm2_5_queue = VecDeque::<i32>::with_capacity(30);
-        let mut pm10_queue = VecDeque::<i32>::with_capacity(30);
-        thread::spawn(move || {
-            loop {
+        //The duty cycle is measured back from the publish tick at the end of the interval, power up a minute before and sample the last 30 seconds
+        let until_publish = tick.until_publish.as_secs();
 
-                // The datasheet says the sensor has a lifespan of 8000 hours, which if we left it on all the time would not last us very long, about a year.
-                // So to extend the life we are going to basically go with a dutycycle of being on for one minute out of every five minutes
-                // This should result in 24hrs * 60mins = 1440 mins/day
-                // 1440 / 5 = 288 five min intervals, where we are on for one minute.
-                // Which should add up to being on for 288 mins a day
-                // 8000 hrs = 480000 mins, 480000/288 = 1666 days or about 4.5 years
+        let port = match self.port {
+            Some(ref mut port) => port,
+            None => {
+                return Err(Error::NotInitialized);
+            }
+        };
 
+        //One minute before the five minute mark turn on the air sensor, this gives 30 seconds to let the sensor stabilize as recommended by datasheet
+        if !self.powered && !tick.publish && until_publish <= 60 {
+            //Send the command to turn on the sensor
+            let cmd = [0xAAu8, 0xB4, 0x06, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0x06, 0xAB];
+            port.write_all(&cmd)?;
+            //These commands seem to take some time to process, so we just wait a little bit before checking for a response
+            thread::sleep(Duration::from_millis(500));
 
-                thread::sleep(Duration::from_millis(1000));
+            //Left unpowered if this fails so the next tick tries again
+            let buf = read_reply(port)?;
+            if buf[1] == 0xC5 && buf[2] == 0x06 && buf[3] == 0x01 && buf[4] == 0x01 {
+                info!("Air Particulate Sensor Successfully Turned On");
+                self.powered = true;
+                self.events.push(Event::new("sds011", EventKind::PoweredOn));
+            } else {
+                return Err(Error::Protocol(format!("Unexpected reply to turning on: {:?}", buf)));
+            }
+        }
 
-                //One minute before the five minute mark turn on the air sensor, this gives 30 seconds to let the sensor stabilize as recommended by datasheet
-                if counter == 240 {
-                    //Send the command to turn on the sensor
-                    let cmd = [0xAAu8, 0xB4, 0x06, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0x06, 0xAB];
-                    match sds011.port.write(&cmd){
-                        Ok(_) => {},
-                        Err(err) => {
-                            //TODO what do we do if this happens???
-                            error!("Failed to write to air sensor: {}", err)
-                        },
-                    }
-                    //These commands seem to take some time to process, so we just wait a little bit before checking for a response
-                    thread::sleep(Duration::from_millis(500));
+        //Accumulate 30 seconds of readings
+        if self.powered && !tick.publish && until_publish <= 30 {
+            //Send the command to query data
+            let cmd = [0xAAu8, 0xB4, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0x02, 0xAB];
+            port.write_all(&cmd)?;
+            //Wait just a little bit before checking result
+            thread::sleep(Duration::from_millis(500));
+            //Read the response
+            let buf = read_reply(port)?;
+            if buf[0] != 0xAA || buf[1] != 0xC0 {
+                return Err(Error::Protocol(format!("Packet from air sensor did not start with the correct bytes: {:?}", buf)));
+            }
+            if self.pm2_5_queue.len() >= 30 {
+                self.pm2_5_queue.truncate(29);
+            }
+            if self.pm10_queue.len() >= 30 {
+                self.pm10_queue.truncate(29);
+            }
+            self.pm2_5_queue.push_front(((buf[3] as i32) << 8) + ((buf[2] as i32) << 0));
+            self.pm10_queue.push_front(((buf[5] as i32) << 8) + ((buf[4] as i32) << 0));
+        }
+        Ok(())
+    }
 
-                    let mut buf: Vec<u8> = (0..20).collect();
-                    match sds011.port.read(&mut buf[..]) {
-                        Ok(t) => {
-                            if t == 10 {
-                                if buf.get(1) == Some(&0xC5) && buf.get(2) == Some(&0x06) && buf.get(3) == Some(&0x01) && buf.get(4) == Some(&0x01) {
-                                    info!("Air Particulate Sensor Successfully Turned On")
-                                } else {
-                                    error!("Failed to turn on air sensor!, Received: {:?}", buf);
-                                }
-                            } else {
-                                error!("Failed to turn on air sensor: Packet from air sensor was incorrect length: {:?}", buf);
-                            }
-                        }
-                        Err(e) => {
-                            error!("Failed to read from air sensor serial port: {}", e);
-                        }
-                    }
+    fn aggregate(&mut self, tick: &Tick) -> Result<Vec<Reading>, Error> {
+        //The fan goes off whatever happened this duty cycle, the readings are still good if it doesn't answer
+        if let Some(ref mut port) = self.port {
+            self.powered = false;
+            match turn_off(port) {
+                Ok(_) => {
+                    self.events.push(Event::new("sds011", EventKind::PoweredOff));
                 }
-
-                //Accumulate 30 seconds of readings
-                if counter >= 270 && counter < 300 {
-                    //Send the command to query data
-                    let cmd = [0xAAu8, 0xB4, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0x02, 0xAB];
-                    match sds011.port.write(&cmd){
-                        Ok(_) => {},
-                        Err(err) => {
-                            //TODO what do we do if this happens???
-                            error!("Failed to write to air sensor: {}", err)
-                        },
-                    }
-                    //Wait just a little bit before checking result
-                    thread::sleep(Duration::from_millis(500));
-                    //Read the response
-                    let mut buf: Vec<u8> = (0..20).collect();
-                    match sds011.port.read(&mut buf[..]) {
-                        Ok(t) => {
-                            if t == 10 && buf.get(0) == Some(&0xAA) && buf.get(1) == Some(&0xC0){
-                                if pm2_5_queue.len() >= 30 {
-                                    pm2_5_queue.truncate(29);
-                                }
-                                if pm10_queue.len() >= 30 {
-                                    pm10_queue.truncate(29);
-                                }
-                                pm2_5_queue.push_front(((buf[3] as i32) << 8) + ((buf[2] as i32) << 0));
-                                pm10_queue.push_front(((buf[5] as i32) << 8) + ((buf[4] as i32) << 0));
-                            } else {
-                                warn!("Packet from air sensor was incorrect length or did not start with the correct bytes: {:?}", buf);
-                            }
-//                            debug!("Received from air sensor: '{:?}'", buf);
-                        }
-                        Err(e) => {
-                            warn!("Failed to read from air sensor serial port: {}", e);
-                        }
-                    }
+                Err(err) => {
+                    error!("Failed to turn off air sensor after its duty cycle: {}", err);
                 }
+            }
+        }
 
-                //Every five minutes take the samples from the air particulate sensor and send them
-                if counter >= 300 {
-                    debug!("2.5 Queue: {:?}", pm2_5_queue);
-                    let mut sum: i32 = 0;
-                    for val in &pm2_5_queue {
-                        sum = sum + val;
-                    }
-                    let pm2_5 = sum / pm2_5_queue.len() as i32;
-
-                    debug!("10 Queue: {:?}", pm10_queue);
-                    let mut sum: i32 = 0;
-                    for val in &pm10_queue {
-                        sum = sum + val;
-                    }
-                    let pm10 = sum / pm10_queue.len() as i32;
+        //Every five minutes take the samples from the air particulate sensor and send them
+        if self.pm2_5_queue.is_empty() || self.pm10_queue.is_empty() {
+            return Err(Error::Protocol(String::from("No readings were taken from the air sensor during this duty cycle")));
+        }
 
-                    let air_part_val = AirParticulateValue{
-                        timestamp: SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis() as u64,
-                        location: 2,
-                        pm2_5: pm2_5 as i16,
-                        pm10: pm10 as i16,
-                    };
+        debug!("2.5 Queue: {:?}", self.pm2_5_queue);
+        let count = self.pm2_5_queue.len() as i32;
+        let mut sum: i32 = 0;
+        for val in self.pm2_5_queue.drain(..) {
+            sum += val;
+        }
+        let pm2_5 = sum / count;
 
-                    match serde_json::to_string(&air_part_val) {
-                        Ok(val) => {
-                            match sds011.sender.send(Payload{
-                                queue: String::from("/ws/2/grp/air_particulate"),
-                                bytes: val
-                            }){
-                                Ok(_) => {},
-                                Err(err) => {
-                                    error!("Failed to send message to main thread: {}", err);
-                                },
-                            }
-                        }
-                        Err(err) => {
-                            error!("Failed to serialize the AirParticulateValue: {}", err);
-                        }
-                    };
+        debug!("10 Queue: {:?}", self.pm10_queue);
+        let count = self.pm10_queue.len() as i32;
+        let mut sum: i32 = 0;
+        for val in self.pm10_queue.drain(..) {
+            sum += val;
+        }
+        let pm10 = sum / count;
+
+        let timestamp = tick.timestamp;
+        Ok(vec![
+            Reading {
+                sensor: "sds011",
+                quantity: Quantity::Pm2_5,
+                value: pm2_5 as f64,
+                timestamp,
+            },
+            Reading {
+                sensor: "sds011",
+                quantity: Quantity::Pm10,
+                value: pm10 as f64,
+                timestamp,
+            },
+        ])
+    }
 
-                    info!("Turning off air monitor");
-                    let cmd = [0xAAu8, 0xB4, 0x06, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0x05, 0xAB];
-                    match sds011.port.write(&cmd) {
-                        Ok(_) => {},
-                        Err(err) => {
-                            //TODO what do we do if this happens???
-                            error!("Failed to write to air sensor: {}", err)
-                        },
-                    }
+    fn events(&mut self) -> Vec<Event> {
+        mem::replace(&mut self.events, Vec::new())
+    }
 
-                    //These commands seem to take some time to process, so we just wait a little bit before checking for a response
-                    thread::sleep(Duration::from_millis(500));
+    fn shutdown(&mut self) -> Result<(), Error> {
+        //Don't leave the fan running if we are stopped in the middle of a duty cycle
+        match self.port {
+            Some(ref mut port) => turn_off(port),
+            None => Ok(()),
+        }
+    }
 
-                    let mut buf: Vec<u8> = (0..20).collect();
-                    match sds011.port.read(&mut buf[..]) {
-                        Ok(t) => {
-                            if t == 10 {
-                                if buf.get(1) == Some(&0xC5) && buf.get(2) == Some(&0x06) && buf.get(3) == Some(&0x01) && buf.get(4) == Some(&0x00) {
-                                    info!("Air Particulate Sensor Successfully Turned Off")
-                                } else {
-                                    error!("Failed to turn off air sensor!, Received: {:?}", buf);
-                                }
-                            } else {
-                                error!("Failed to turn off air sensor: Packet from air sensor was incorrect length: {:?}", buf);
-                            }
-                        }
-                        Err(e) => {
-                            error!("Failed to read from air sensor serial port: {}", e);
-                        }
-                    }
+    fn command(&mut self, command: &Command) -> Result<String, Error> {
+        match *command {
+            Command::SetInterval { interval_secs } => {
+                //The duty cycle needs a minute before every publish
+                if interval_secs < 60 {
+                    return Err(Error::from(ConfigError::Invalid(String::from("sds011 interval_secs must be at least 60"))));
+                }
+                self.config.interval_secs = interval_secs;
+                Ok(format!("Readings will be sent every {} seconds", interval_secs))
+            }
+            _ => Err(Error::Unsupported),
+        }
+    }
+}
 
+fn turn_off(port: &mut TTYPort) -> Result<(), Error> {
+    info!("Turning off air monitor");
+    let cmd = [0xAAu8, 0xB4, 0x06, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0x05, 0xAB];
+    port.write_all(&cmd)?;
 
-                    //Reset the counter after the five minute mark
-                    counter = 0;
-                }
+    //These commands seem to take some time to process, so we just wait a little bit before checking for a response
+    thread::sleep(Duration::from_millis(500));
 
-                counter = counter + 1;
-            }
+    let buf = read_reply(port)?;
+    if buf[1] == 0xC5 && buf[2] == 0x06 && buf[3] == 0x01 && buf[4] == 0x00 {
+        info!("Air Particulate Sensor Successfully Turned Off");
+        Ok(())
+    } else {
+        Err(Error::Protocol(format!("Unexpected reply to turning off: {:?}", buf)))
+    }
+}
 
-        });
+/// Reads one 10 byte frame, nothing arriving before the port's timeout is an `Error::Timeout`
+fn read_reply(port: &mut TTYPort) -> Result<[u8; 10], Error> {
+    let mut buf = [0u8; 20];
+    let t = port.read(&mut buf[..])?;
+    if t == 0 {
+        return Err(Error::Timeout);
+    }
+    if t != 10 {
+        return Err(Error::Protocol(format!("Packet from air sensor was incorrect length: {:?}", &buf[..t])));
     }
-}
\ No newline at end of file
+    let mut frame = [0u8; 10];
+    frame.copy_from_slice(&buf[..10]);
+    Ok(frame)
+}
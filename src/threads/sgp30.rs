@@ -1,104 +1,151 @@
-use std::fmt::{Display, Formatter};
-
-use std::sync::mpsc::Sender;
 use std::sync::{Arc, Mutex};
-use std::thread;
-use std::time::SystemTime;
 use std::time::Duration;
 use std::f64::consts::E;
-use std::f32::NAN;
 use std::fs;
-use std::path::Path;
-use std::io::Error as IoError;
-use std::num::ParseIntError;
+use std::path::{Path, PathBuf};
 use std::collections::VecDeque;
+use std::mem;
 
 
-use Payload;
-use sensor_lib::SensorValue;
+use bus::{BusManager, I2cProxy};
+use command::Command;
+use config::Sgp30Config;
+use error::Error;
+use payload::{Reading, Quantity, Event, EventKind};
+use sync::lock;
+use threads::{SensorDriver, Description, Tick};
 
-use linux_hal::{I2cdev, Delay};
+use linux_hal::Delay;
 use linux_hal::i2cdev::linux::LinuxI2CError;
 
 use sgp30::{Sgp30 as Sgp, Humidity, Error as SgpError, Baseline};
 
-#[derive(Debug)]
-pub struct Error {
-    message: String,
+impl From<SgpError<LinuxI2CError>> for Error {
+    fn from(err: SgpError<LinuxI2CError>) -> Self {
+        match err {
+            SgpError::I2c(i2c_error) => Error::from(i2c_error),
+            SgpError::Crc => Error::Crc,
+            SgpError::NotInitialized => Error::NotInitialized,
+        }
+    }
+}
+
+pub struct Sgp30 {
+    buses: Arc<BusManager>,
+    sgp30: Option<Sgp<I2cProxy, Delay>>,
+    humidity_mutex: Arc<Mutex<(f32, f32)>>,
+    config: Sgp30Config,
+    co2_path: PathBuf,
+    tvoc_path: PathBuf,
+    co2_queue: VecDeque<u16>,
+    voc_queue: VecDeque<u16>,
+    events: Vec<Event>,
 }
 
-impl From<LinuxI2CError> for Error {
-    fn from(err: LinuxI2CError) -> Self {
-        Error {
-            message: format!("I2C Error: {}", err),
+impl Sgp30 {
+    pub fn new(buses: Arc<BusManager>, humidity_mutex: Arc<Mutex<(f32, f32)>>, config: Sgp30Config) -> Sgp30 {
+        let co2_path = Path::new(&config.baseline_dir).join("sgp30_co2.txt");
+        let tvoc_path = Path::new(&config.baseline_dir).join("sgp30_tvoc.txt");
+
+        Sgp30 {
+            buses,
+            sgp30: None,
+            humidity_mutex,
+            config,
+            co2_path,
+            tvoc_path,
+            co2_queue: VecDeque::<u16>::with_capacity(60),
+            voc_queue: VecDeque::<u16>::with_capacity(60),
+            events: Vec::new(),
         }
     }
-}
 
-impl From<SgpError<LinuxI2CError>> for Error {
-    fn from(err: SgpError<LinuxI2CError>) -> Self {
-        match err {
-            SgpError::I2c(i2c_error) => {
-                Error {
-                    message: format!("SGP30 I2C Error: {}", i2c_error),
-                }
-            }
-            SgpError::Crc => {
-                Error {
-                    message: String::from("CRC Error"),
+    //This equation for absolute humidity comes from: https://carnotcycle.wordpress.com/2012/08/04/how-to-convert-relative-humidity-to-absolute-humidity/
+    fn update_humidity(&mut self) {
+        let sgp30 = match self.sgp30 {
+            Some(ref mut sgp30) => sgp30,
+            None => return,
+        };
+
+        let (temp, humidity) = *lock(&self.humidity_mutex);
+
+        if !temp.is_nan() && !humidity.is_nan() {
+            let abs_humidity = (6.112 * E.powf(((17.67 * temp) / (temp + 243.5)) as f64) as f32 * humidity * 2.1674) as f32 / (273.15 + temp);
+            match Humidity::from_f32(abs_humidity) {
+                Ok(sgp30_humidity) => {
+                    match sgp30.set_humidity(Some(&sgp30_humidity)) {
+                        Ok(_) => {
+                            debug!("Set SGP abs humidity to: {} from a temp val of {} and humidity val of {}", abs_humidity, temp, humidity);
+                        }
+                        Err(err) => {
+                            error!("Failed to update the humidity value of the sgp30: {:?}", err);
+                        }
+                    }
                 }
-            }
-            SgpError::NotInitialized => {
-                Error {
-                    message: String::from("Not Initialized"),
+                Err(err) => {
+                    error!("Failed to create a humidity value for SGP30: {:?}", err);
                 }
             }
         }
     }
-}
 
-impl From<IoError> for Error {
-    fn from(err: IoError) -> Self {
-        Error {
-            message: format!("{}", err),
-        }
+    fn save_baseline(&mut self) -> Result<Baseline, Error> {
+        let sgp30 = match self.sgp30 {
+            Some(ref mut sgp30) => sgp30,
+            None => return Err(Error::NotInitialized),
+        };
+
+        let base = sgp30.get_baseline()?;
+        fs::write(&self.co2_path, base.co2eq.to_string())?;
+        fs::write(&self.tvoc_path, base.tvoc.to_string())?;
+        debug!("Saved CO2 baseline of {} and TVOC baseline of {}", base.co2eq, base.tvoc);
+        self.events.push(Event::new("sgp30", EventKind::BaselineSaved { co2: base.co2eq, tvoc: base.tvoc }));
+        Ok(base)
     }
-}
 
-impl From<ParseIntError> for Error {
-    fn from(err: ParseIntError) -> Self {
-        Error {
-            message: format!("{}", err),
+    /// Forgets the saved baseline and starts the sensor learning a new one, it takes 12 hours to be any good
+    fn reset_baseline(&mut self) -> Result<(), Error> {
+        let sgp30 = match self.sgp30 {
+            Some(ref mut sgp30) => sgp30,
+            None => return Err(Error::NotInitialized),
+        };
+
+        for path in &[&self.co2_path, &self.tvoc_path] {
+            if path.exists() {
+                fs::remove_file(path)?;
+            }
         }
+        //Initializing the air quality algorithm again throws away what it has learned
+        sgp30.init()?;
+        info!("Reset the SGP30 baseline, it will be learned again from scratch");
+        self.events.push(Event::new("sgp30", EventKind::BaselineReset));
+        Ok(())
     }
 }
 
-impl Display for Error {
-    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
-        write!(f, "{}", self.message)
+impl SensorDriver for Sgp30 {
+    fn describe(&self) -> Description {
+        Description {
+            name: "sgp30",
+            // The driver for the SGP30 says we have to call the measure() function once a second
+            // for the dynamic baseline to work properly, so we sample on a one sec delay
+            // but we don't really need one sec resolution on this sensor, so we average the samples and
+            // only send them once every interval (a minute by default).
+            // Ticks are scheduled on the clock, so the 12 ms a measurement takes doesn't push the next one back
+            sample_interval: Duration::from_millis(1000),
+            publish_interval: Duration::from_secs(self.config.interval_secs),
+            warmup: Duration::from_secs(0),
+        }
     }
-}
 
-pub struct Sgp30 {
-    sender: Sender<Payload>,
-    lock: Arc<Mutex<i32>>,
-    sgp30: Sgp<I2cdev, Delay>,
-    humidity_mutex: Arc<Mutex<(f32, f32)>>,
-}
-
-impl Sgp30 {
-    pub fn new(sender: Sender<Payload>, lock: Arc<Mutex<i32>>, humidity_mutex: Arc<Mutex<(f32, f32)>>) -> Result<Sgp30, Error> {
+    fn init(&mut self) -> Result<(), Error> {
         info!("Create and Init SGP30");
-        let dev2 = I2cdev::new("/dev/i2c-1")?;
-        let address = 0x58;
-        let mut sgp30 = Sgp::new(dev2, address, Delay);
+        let dev = self.buses.proxy(&self.config.device, self.config.address)?;
+        let mut sgp30 = Sgp::new(dev, self.config.address, Delay);
         sgp30.init()?;
 
-        let co2_path = Path::new("/var/lib/indoor_sensors/sgp30_co2.txt");
-        let tvoc_path = Path::new("/var/lib/indoor_sensors/sgp30_tvoc.txt");
-
-        if co2_path.exists() && tvoc_path.exists() {
-            match read_baseline(&co2_path, &tvoc_path) {
+        if self.co2_path.exists() && self.tvoc_path.exists() {
+            match read_baseline(&self.co2_path, &self.tvoc_path) {
                 Ok(baseline) => {
                     sgp30.set_baseline(&baseline)?;
                 }
@@ -110,210 +157,110 @@ impl Sgp30 {
             info!("No existing baseline files found for SGP30, no baseline will be used")
         }
 
-        Ok(Sgp30 {
-            sender,
-            lock,
-            sgp30,
-            humidity_mutex,
-        })
+        self.sgp30 = Some(sgp30);
+        Ok(())
     }
 
-    pub fn start_thread(mut sgp: Sgp30) {
-        thread::spawn(move || {
-            info!("Started SGP30 Thread");
-            let mut counter = 1;
-            let mut co2_queue = VecDeque::<u16>::with_capacity(60);
-            let mut voc_queue = VecDeque::<u16>::with_capacity(60);
-            loop {
-
-                //////////////////////////
-                // CO2 and VOC
-                //////////////////////////
-
-
-                // The driver for the SGP30 says we have to call the measure() function once a second
-                // for the dynamic baseline to work properly, the main loop runs on a one sec delay
-                // but we don't really need one sec resolution on this sensor, so we call the measure function
-                // but usually ignore the result.
-                // A measurement is supposed to take 12 ms so we will subtract that from our sleep time
-                thread::sleep(Duration::from_millis(1000 - 12));
-
-                let mut measurement = None;
-                match sgp.lock.lock() {
-                    Ok(_) => {
-                        match sgp.sgp30.measure() {
-                            Ok(val) => {
-                                measurement = Some(val);
-                            }
-                            Err(err) => {
-                                error!("Failed to read from SGP30: {:?}", err);
-                            }
-                        }
-                    }
-                    Err(_) => {
-                        error!("The lock has been poisoned, sending a poison message to kill the app");
-                        sgp.sender.send(Payload {
-                            queue: String::from("poison"),
-                            bytes: String::from("poison"),
-                        }).unwrap(); //We don't really care anymore if this thread panics
-                    }
-                }
+    fn sample(&mut self, _tick: &Tick) -> Result<(), Error> {
 
-                if let Some(meas) = measurement {
-                    if co2_queue.len() >= 60 {
-                        co2_queue.truncate(59);
-                    }
-                    if voc_queue.len() >= 60 {
-                        voc_queue.truncate(59);
-                    }
-                    co2_queue.push_front(meas.co2eq_ppm);
-                    voc_queue.push_front(meas.tvoc_ppb);
-                }
+        //////////////////////////
+        // CO2 and VOC
+        //////////////////////////
 
-                //Even though we read the value once a second, only send it once a minute
-                if counter >= 60 {
-                    debug!("CO2 Vals: {:?}", co2_queue);
-                    debug!("VOC Vals: {:?}", voc_queue);
+        let meas = match self.sgp30 {
+            Some(ref mut sgp30) => sgp30.measure()?,
+            None => {
+                return Err(Error::NotInitialized);
+            }
+        };
 
-                    let mut sum = 0u32;
-                    for val in &co2_queue {
-                        sum = sum + *val as u32;
-                    }
-                    let co2_avg = sum / co2_queue.len() as u32;
+        if self.co2_queue.len() >= 60 {
+            self.co2_queue.truncate(59);
+        }
+        if self.voc_queue.len() >= 60 {
+            self.voc_queue.truncate(59);
+        }
+        self.co2_queue.push_front(meas.co2eq_ppm);
+        self.voc_queue.push_front(meas.tvoc_ppb);
+        Ok(())
+    }
 
-                    let mut sum = 0u32;
-                    for val in &voc_queue {
-                        sum = sum + *val as u32;
-                    }
+    fn aggregate(&mut self, tick: &Tick) -> Result<Vec<Reading>, Error> {
+        let mut readings = Vec::new();
 
-                    let voc_avg = sum / voc_queue.len() as u32;
-
-                    let temp_val = SensorValue {
-                        id: 52,
-                        timestamp: SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis() as u64,
-                        value: co2_avg.to_string(),
-                    };
-
-                    match serde_json::to_string(&temp_val) {
-                        Ok(val) => {
-                            match sgp.sender.send(Payload {
-                                queue: String::from("/ws/2/grp/generic"),
-                                bytes: val,
-                            }) {
-                                Ok(_) => {}
-                                Err(err) => {
-                                    error!("Failed to send message to main thread: {}", err);
-                                }
-                            }
-                        }
-                        Err(err) => {
-                            error!("Failed to serialize the sensor value: {}", err);
-                        }
-                    };
-
-
-                    let temp_val = SensorValue {
-                        id: 53,
-                        timestamp: SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis() as u64,
-                        value: voc_avg.to_string(),
-                    };
-
-
-                    match serde_json::to_string(&temp_val) {
-                        Ok(val) => {
-                            match sgp.sender.send(Payload {
-                                queue: String::from("/ws/2/grp/generic"),
-                                bytes: val,
-                            }) {
-                                Ok(_) => {}
-                                Err(err) => {
-                                    error!("Failed to send message to main thread: {}", err);
-                                }
-                            }
-                        }
-                        Err(err) => {
-                            error!("Failed to serialize the sensor value: {}", err);
-                        }
-                    };
+        if self.co2_queue.is_empty() || self.voc_queue.is_empty() {
+            return Err(Error::Protocol(String::from("No measurements were read since the last interval")));
+        }
 
+        debug!("CO2 Vals: {:?}", self.co2_queue);
+        debug!("VOC Vals: {:?}", self.voc_queue);
 
-                    //Update the humidity value for the next set of readings:
-                    //This equation for absolute humidity comes from: https://carnotcycle.wordpress.com/2012/08/04/how-to-convert-relative-humidity-to-absolute-humidity/
-                    let mut temp = NAN;
-                    let mut humidity = NAN;
-                    match sgp.humidity_mutex.lock() {
-                        Ok(mut_val) => {
-                            temp = mut_val.0;
-                            humidity = mut_val.1;
-                        }
-                        Err(_) => {}
-                    }
+        let mut sum = 0u32;
+        for val in &self.co2_queue {
+            sum += *val as u32;
+        }
+        let co2_avg = sum / self.co2_queue.len() as u32;
 
-                    if !temp.is_nan() && !humidity.is_nan() {
-                        let abs_humidity = (6.112 * E.powf(((17.67 * temp) / (temp + 243.5)) as f64) as f32 * humidity * 2.1674) as f32 / (273.15 + temp);
-                        match Humidity::from_f32(abs_humidity) {
-                            Ok(sgp30_humidity) => {
-                                match sgp.lock.lock() {
-                                    Ok(_) => {
-                                        match sgp.sgp30.set_humidity(Some(&sgp30_humidity)) {
-                                            Ok(_) => {
-                                                debug!("Set SGP abs humidity to: {} from a temp val of {} and humidity val of {}", abs_humidity, temp, humidity);
-                                            }
-                                            Err(err) => {
-                                                error!("Failed to update the humidity value of the sgp30: {:?}", err);
-                                            }
-                                        }
-                                    }
-                                    Err(_) => {
-                                        //I'm ignoring this lock failure because we will catch it above if the lock is poisoned
-                                    }
-                                }
-                            }
-                            Err(err) => {
-                                error!("Failed to create a humidity value for SGP30: {:?}", err);
-                            }
-                        }
-                    }
+        let mut sum = 0u32;
+        for val in &self.voc_queue {
+            sum += *val as u32;
+        }
 
-                    //Save off the baseline data:
-                    let mut baseline = None;
-                    match sgp.lock.lock() {
-                        Ok(_) => {
-                            match sgp.sgp30.get_baseline() {
-                                Ok(incoming_baseline) => {
-                                    baseline = Some(incoming_baseline);
-                                }
-                                Err(err) => {
-                                    error!("Failed to read the baseline data from the sgp30: {:?}", err);
-                                }
-                            }
-                        }
-                        Err(_) => {
-                            //I'm ignoring this lock failure because we will catch it above if the lock is poisoned
-                        }
-                    }
+        let voc_avg = sum / self.voc_queue.len() as u32;
 
-                    if let Some(base) = baseline {
-                        match fs::write("/var/lib/indoor_sensors/sgp30_co2.txt", base.co2eq.to_string()) {
-                            Ok(_) => {}
-                            Err(err) => {
-                                error!("Failed to save sgp30 baseline to a file: {}", err);
-                            }
-                        }
-                        match fs::write("/var/lib/indoor_sensors/sgp30_tvoc.txt", base.tvoc.to_string()) {
-                            Ok(_) => {}
-                            Err(err) => {
-                                error!("Failed to save sgp30 baseline to a file: {}", err);
-                            }
-                        }
-                        debug!("Saved CO2 baseline of {} and TVOC baseline of {}", base.co2eq, base.tvoc);
-                    }
+        let timestamp = tick.timestamp;
 
-                    counter = 0;
-                }
-                counter = counter + 1;
-            }
+        readings.push(Reading {
+            sensor: "sgp30",
+            quantity: Quantity::Co2,
+            value: co2_avg as f64,
+            timestamp,
+        });
+
+        readings.push(Reading {
+            sensor: "sgp30",
+            quantity: Quantity::Tvoc,
+            value: voc_avg as f64,
+            timestamp,
         });
+
+        //Update the humidity value for the next set of readings:
+        self.update_humidity();
+
+        //Save off the baseline data:
+        if let Err(err) = self.save_baseline() {
+            error!("Failed to save the sgp30 baseline: {}", err);
+        }
+
+        Ok(readings)
+    }
+
+    fn shutdown(&mut self) -> Result<(), Error> {
+        //Keep the latest baseline so the next start doesn't have to learn it again
+        self.save_baseline()?;
+        Ok(())
+    }
+
+    fn events(&mut self) -> Vec<Event> {
+        mem::replace(&mut self.events, Vec::new())
+    }
+
+    fn command(&mut self, command: &Command) -> Result<String, Error> {
+        match *command {
+            Command::SaveBaseline => {
+                let base = self.save_baseline()?;
+                Ok(format!("Saved CO2 baseline of {} and TVOC baseline of {}", base.co2eq, base.tvoc))
+            }
+            Command::ResetBaseline => {
+                self.reset_baseline()?;
+                Ok(String::from("Baseline reset, it will be learned again from scratch"))
+            }
+            Command::SetInterval { interval_secs } => {
+                self.config.interval_secs = interval_secs;
+                Ok(format!("Readings will be sent every {} seconds", interval_secs))
+            }
+            _ => Err(Error::Unsupported),
+        }
     }
 }
 
@@ -0,0 +1,287 @@
+use std::cmp;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use command::Request;
+use error::Error;
+use payload::{Payload, Status, Control};
+use shutdown::Shutdown;
+use threads::{SensorDriver, runner};
+use threads::runner::send_health;
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(5);
+const MAX_BACKOFF: Duration = Duration::from_secs(600);
+//A sample can legitimately take a while (the thermostat has a 20 second timeout), give every worker this much
+//slack on top of its own interval before calling it stalled
+const STALL_GRACE: Duration = Duration::from_secs(60);
+
+/// Shared between a worker thread and the supervisor, the thread beats once per loop and the
+/// supervisor uses it to tell a dead or stalled worker from one that is just sleeping.
+#[derive(Clone)]
+pub struct Heartbeat {
+    //Beats are counted from here rather than by the wall clock, which NTP steps when a Pi without an RTC boots
+    epoch: Instant,
+    //Milliseconds since the epoch
+    last_beat: Arc<AtomicU64>,
+    //Milliseconds without a beat before the worker counts as stalled
+    stall_after: Arc<AtomicU64>,
+    stopped: Arc<AtomicBool>,
+    abandoned: Arc<AtomicBool>,
+}
+
+impl Heartbeat {
+    fn new(sample_interval: Duration) -> Heartbeat {
+        let heartbeat = Heartbeat {
+            epoch: Instant::now(),
+            last_beat: Arc::new(AtomicU64::new(0)),
+            stall_after: Arc::new(AtomicU64::new(0)),
+            stopped: Arc::new(AtomicBool::new(false)),
+            abandoned: Arc::new(AtomicBool::new(false)),
+        };
+        heartbeat.expect_every(sample_interval);
+        heartbeat
+    }
+
+    pub fn beat(&self) {
+        self.last_beat.store(self.elapsed_millis(), Ordering::SeqCst);
+    }
+
+    /// The runner beats once per sample, it tells us again whenever a command changes its interval
+    pub fn expect_every(&self, sample_interval: Duration) {
+        self.stall_after.store((sample_interval * 2 + STALL_GRACE).as_millis() as u64, Ordering::SeqCst);
+    }
+
+    /// Called by the runner when its thread exits, whether it returned or panicked
+    pub fn stop(&self) {
+        self.stopped.store(true, Ordering::SeqCst);
+    }
+
+    /// A stalled worker is replaced, if it ever wakes up it should exit without doing anything else
+    pub fn is_abandoned(&self) -> bool {
+        self.abandoned.load(Ordering::SeqCst)
+    }
+
+    fn elapsed_millis(&self) -> u64 {
+        self.epoch.elapsed().as_millis() as u64
+    }
+
+    fn millis_since_beat(&self) -> u64 {
+        self.elapsed_millis().saturating_sub(self.last_beat.load(Ordering::SeqCst))
+    }
+
+    fn is_stalled(&self) -> bool {
+        self.millis_since_beat() > self.stall_after.load(Ordering::SeqCst)
+    }
+}
+
+type StartFn = Box<dyn Fn(Sender<Payload>, Receiver<Request>, Shutdown, Heartbeat) -> Result<JoinHandle<()>, Error>>;
+
+struct Worker {
+    name: &'static str,
+    start: StartFn,
+    sample_interval: Duration,
+    handle: Option<JoinHandle<()>>,
+    heartbeat: Heartbeat,
+    //Commands for the running thread, dropping it wakes the thread up
+    commands: Option<Sender<Request>>,
+    started_at: Instant,
+    restarts: u32,
+    backoff: Duration,
+    //Set while the worker is down and waiting for its backoff to run out
+    restart_at: Option<Instant>,
+    //The sensor has never been initialized, it is probed every reprobe interval instead of backing off
+    missing: bool,
+}
+
+/// Owns every sensor worker thread. A worker which panics or stops beating is restarted with
+/// a fresh driver after an exponential backoff so one misbehaving sensor can't take down the others.
+pub struct Supervisor {
+    workers: Vec<Worker>,
+    sender: Sender<Payload>,
+    shutdown: Shutdown,
+    reprobe_interval: Duration,
+}
+
+impl Supervisor {
+    pub fn new(sender: Sender<Payload>, shutdown: Shutdown, reprobe_interval: Duration) -> Supervisor {
+        Supervisor {
+            workers: Vec::new(),
+            sender,
+            shutdown,
+            reprobe_interval,
+        }
+    }
+
+    /// Creates a driver with `create`, initializes it and starts its thread. `create` is kept so the
+    /// worker can be rebuilt from scratch on a restart, or probed again later if the device is missing.
+    /// A required sensor which can't be started is fatal, main is told and exits.
+    pub fn start<D, F>(&mut self, create: F, required: bool)
+        where D: SensorDriver + 'static,
+              F: Fn() -> D + 'static {
+        let description = create().describe();
+        let name = description.name;
+        let reprobe_interval = self.reprobe_interval;
+        let start: StartFn = Box::new(move |sender, commands, shutdown, heartbeat| {
+            let mut driver = create();
+            driver.init()?;
+            Ok(runner::start_thread(driver, sender, commands, shutdown, heartbeat, reprobe_interval))
+        });
+
+        let mut worker = Worker {
+            name,
+            start,
+            sample_interval: description.sample_interval,
+            handle: None,
+            heartbeat: Heartbeat::new(description.sample_interval),
+            commands: None,
+            started_at: Instant::now(),
+            restarts: 0,
+            backoff: INITIAL_BACKOFF,
+            restart_at: None,
+            missing: false,
+        };
+        let (commands, receiver) = mpsc::channel();
+        match (worker.start)(Sender::clone(&self.sender), receiver, self.shutdown.clone(), worker.heartbeat.clone()) {
+            Ok(handle) => {
+                worker.handle = Some(handle);
+                worker.commands = Some(commands);
+                send_health(&self.sender, name, Status::Up);
+            }
+            Err(err) => {
+                if required {
+                    let reason = format!("Failed to create required sensor {}: {}", name, err);
+                    if let Err(err) = self.sender.send(Payload::Control(Control::Fatal(reason))) {
+                        error!("Failed to send message to main thread: {}", err);
+                    }
+                    return;
+                }
+                error!("Failed to create {}: {}, will look for it again every {} seconds", name, err, self.reprobe_interval.as_secs());
+                worker.missing = true;
+                worker.restart_at = Some(Instant::now() + self.reprobe_interval);
+                send_health(&self.sender, name, Status::Failed(err.to_string()));
+            }
+        }
+        self.workers.push(worker);
+    }
+
+    /// Called regularly from the main loop, notices dead or stalled workers and restarts any whose backoff has run out.
+    pub fn check(&mut self) {
+        //Workers stopping because we asked them to is not a failure
+        if self.shutdown.is_triggered() {
+            return;
+        }
+        for worker in self.workers.iter_mut() {
+            if let Some(restart_at) = worker.restart_at {
+                if Instant::now() >= restart_at {
+                    restart(worker, &self.sender, &self.shutdown, self.reprobe_interval);
+                }
+                continue;
+            }
+
+            let reason = if worker.heartbeat.stopped.load(Ordering::SeqCst) {
+                //Join it so a panic is logged with the rest of the restart, it has already exited so this won't block
+                if let Some(handle) = worker.handle.take() {
+                    let _ = handle.join();
+                }
+                worker.commands.take();
+                String::from("Thread stopped unexpectedly")
+            } else if worker.heartbeat.is_stalled() {
+                //There's no way to kill a thread, let the stuck one go and make sure it exits if it ever wakes up
+                worker.heartbeat.abandoned.store(true, Ordering::SeqCst);
+                worker.handle.take();
+                worker.commands.take();
+                format!("Thread stalled, no heartbeat for {} seconds", worker.heartbeat.millis_since_beat() / 1000)
+            } else {
+                continue;
+            };
+
+            //A worker which stayed up for a good while is having a new problem, not the same one again
+            if worker.started_at.elapsed() >= MAX_BACKOFF {
+                worker.backoff = INITIAL_BACKOFF;
+            }
+            error!("{}: {}, restarting it in {} seconds", worker.name, reason, worker.backoff.as_secs());
+            send_health(&self.sender, worker.name, Status::Failed(reason));
+            worker.restart_at = Some(Instant::now() + worker.backoff);
+            worker.backoff = cmp::min(worker.backoff * 2, MAX_BACKOFF);
+        }
+    }
+
+    /// Hands a command to the sensor's thread, the thread sends the reply once it's done. Fails if there
+    /// is no such sensor or it isn't running.
+    pub fn command(&self, request: Request) -> Result<(), String> {
+        let worker = match self.workers.iter().find(|worker| worker.name == request.sensor) {
+            Some(worker) => worker,
+            None => return Err(format!("There is no enabled sensor called {}", request.sensor)),
+        };
+        match worker.commands {
+            Some(ref commands) => commands.send(request).map_err(|_| format!("{} has stopped", worker.name)),
+            None => Err(format!("{} is not running", worker.name)),
+        }
+    }
+
+    /// Waits for every worker to finish after a shutdown has been triggered, stalled workers which were
+    /// abandoned are not waited for.
+    pub fn join(mut self) {
+        //Wakes up every thread which is waiting for its next tick
+        for worker in self.workers.iter_mut() {
+            worker.commands.take();
+        }
+        for worker in self.workers {
+            if let Some(handle) = worker.handle {
+                info!("Waiting for {} to stop, it was restarted {} times", worker.name, worker.restarts);
+                match handle.join() {
+                    Ok(_) => {}
+                    Err(_) => {
+                        error!("The {} thread panicked while stopping", worker.name);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn restart(worker: &mut Worker, sender: &Sender<Payload>, shutdown: &Shutdown, reprobe_interval: Duration) {
+    let heartbeat = Heartbeat::new(worker.sample_interval);
+    let (commands, receiver) = mpsc::channel();
+    let result = (worker.start)(Sender::clone(sender), receiver, shutdown.clone(), heartbeat.clone());
+    if worker.missing {
+        match result {
+            Ok(handle) => {
+                info!("Found {}, attaching it", worker.name);
+                worker.handle = Some(handle);
+                worker.heartbeat = heartbeat;
+                worker.commands = Some(commands);
+                worker.started_at = Instant::now();
+                worker.restart_at = None;
+                worker.missing = false;
+                send_health(sender, worker.name, Status::Up);
+            }
+            Err(err) => {
+                debug!("{} is still missing: {}", worker.name, err);
+                worker.restart_at = Some(Instant::now() + reprobe_interval);
+            }
+        }
+        return;
+    }
+
+    worker.restarts += 1;
+    match result {
+        Ok(handle) => {
+            info!("Restarted {}, it has been restarted {} times", worker.name, worker.restarts);
+            worker.handle = Some(handle);
+            worker.heartbeat = heartbeat;
+            worker.commands = Some(commands);
+            worker.started_at = Instant::now();
+            worker.restart_at = None;
+            send_health(sender, worker.name, Status::Restarted(worker.restarts));
+        }
+        Err(err) => {
+            error!("Failed to restart {}: {}, trying again in {} seconds", worker.name, err, worker.backoff.as_secs());
+            worker.restart_at = Some(Instant::now() + worker.backoff);
+            worker.backoff = cmp::min(worker.backoff * 2, MAX_BACKOFF);
+        }
+    }
+}